@@ -0,0 +1,76 @@
+use maud::{html, Markup, Render};
+
+use crate::theme::{default_theme, Theme};
+
+///Élément accolé à un champ dans un input-group
+#[derive(Clone, Debug)]
+pub enum Addon {
+    Text(String),
+    Icon(Markup),
+    ///Bouton (ou tout autre markup) inséré tel quel dans le groupe
+    Button(Markup),
+}
+
+impl Addon {
+    pub fn text(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+
+    pub fn icon(icon: Markup) -> Self {
+        Self::Icon(icon)
+    }
+
+    pub fn button(button: Markup) -> Self {
+        Self::Button(button)
+    }
+
+    pub fn render_with(&self, theme: &dyn Theme) -> Markup {
+        let class = Some(theme.input_group_text_class()).filter(|x| !x.is_empty());
+
+        match self {
+            Self::Text(text) => html!(span class=[class] {(text)}),
+            Self::Icon(icon) => html!(span class=[class] {(icon)}),
+            Self::Button(button) => button.clone(),
+        }
+    }
+}
+
+impl Render for Addon {
+    fn render(&self) -> Markup {
+        self.render_with(default_theme().as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            Addon::text("€").render().into_string(),
+            r#"<span class="input-group-text">€</span>"#
+        );
+    }
+
+    #[test]
+    fn icon() {
+        let icon = Addon::icon(html!(i class="bi bi-at" {}));
+
+        assert_eq!(
+            icon.render().into_string(),
+            r#"<span class="input-group-text"><i class="bi bi-at"></i></span>"#
+        );
+    }
+
+    #[test]
+    fn button() {
+        let button =
+            Addon::button(html!(button class="btn btn-outline-secondary" type="button" {"Go"}));
+
+        assert_eq!(
+            button.render().into_string(),
+            r#"<button class="btn btn-outline-secondary" type="button">Go</button>"#
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::{self, Write};
+
+use maud::{Escaper, Markup, PreEscaped};
+
+///Liste ordonnée d'attributs html, pour les noms non connus à la compilation (hx-*, data-*)
+///
+///L'ordre d'insertion est conservé, un attribut redéfini garde sa place.
+///Un nom invalide (vide, espace, `=`, `/`, `>`, guillemet ou caractère de contrôle) est ignoré.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attributes {
+    items: Vec<(String, Option<String>)>,
+}
+
+impl Attributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set<V: ToString>(self, name: &str, value: V) -> Self {
+        self.insert(name, Some(value.to_string()))
+    }
+
+    pub fn set_opt<V: ToString>(self, name: &str, value: Option<V>) -> Self {
+        match value {
+            Some(value) => self.set(name, value),
+            None => self,
+        }
+    }
+
+    ///Attribut booléen (sans valeur), ajouté seulement si on vaut true
+    pub fn flag(self, name: &str, on: bool) -> Self {
+        match on {
+            true => self.insert(name, None),
+            false => self,
+        }
+    }
+
+    pub fn extend(self, other: &Attributes) -> Self {
+        other
+            .items
+            .iter()
+            .fold(self, |acc, (name, value)| acc.insert(name, value.clone()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<Option<&str>> {
+        self.items
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_deref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn insert(self, name: &str, value: Option<String>) -> Self {
+        if !is_valid_name(name) {
+            return self;
+        }
+
+        let mut items = self.items;
+
+        match items.iter_mut().find(|(key, _)| key == name) {
+            Some(item) => item.1 = value,
+            None => items.push((name.to_owned(), value)),
+        }
+
+        Self { items }
+    }
+}
+
+///Attribut que le composant rend lui-même et que attr ou flag ne remplacent pas
+pub(crate) fn is_reserved(name: &str) -> bool {
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "name" | "class" | "type" | "value"
+    )
+}
+
+///Nom d'attribut html sans caractère pouvant fermer la balise ou introduire un autre attribut
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '=' | '/' | '>' | '<' | '"' | '\'')
+        })
+}
+
+impl fmt::Display for Attributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.items {
+            let mut escaped = String::new();
+            Escaper::new(&mut escaped).write_str(name)?;
+            write!(f, " {escaped}")?;
+
+            if let Some(value) = value {
+                let mut escaped = String::new();
+                Escaper::new(&mut escaped).write_str(value)?;
+                write!(f, "=\"{escaped}\"")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+///Balise sans contenu : `<input ...>`
+pub(crate) fn void_tag(tag: &str, attributes: &Attributes) -> Markup {
+    PreEscaped(format!("<{tag}{attributes}>"))
+}
+
+///Balise avec contenu, le contenu est déjà échappé
+pub(crate) fn tag(tag: &str, attributes: &Attributes, content: Markup) -> Markup {
+    PreEscaped(format!(
+        "<{tag}{attributes}>{}</{tag}>",
+        content.into_string()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use maud::html;
+
+    use super::*;
+
+    #[test]
+    fn order_and_escape() {
+        let attributes = Attributes::new()
+            .set("type", "text")
+            .set("value", r#"a "b" <c>"#)
+            .flag("required", true)
+            .flag("disabled", false)
+            .set("type", "email");
+
+        assert_eq!(
+            void_tag("input", &attributes).into_string(),
+            r#"<input type="email" value="a &quot;b&quot; &lt;c&gt;" required>"#
+        );
+    }
+
+    #[test]
+    fn invalid_names() {
+        let attributes = Attributes::new()
+            .set("onclick x", "1")
+            .set("a=b", "1")
+            .set("x/", "1")
+            .set("x>", "1")
+            .set("\"x", "1")
+            .set("x\n", "1")
+            .flag("", true)
+            .set("hx-get", "/a");
+
+        assert_eq!(attributes.to_string(), r#" hx-get="/a""#);
+    }
+
+    #[test]
+    fn with_content() {
+        let attributes = Attributes::new().set("class", "x");
+
+        assert_eq!(
+            tag("div", &attributes, html!(span { "<" })).into_string(),
+            r#"<div class="x"><span>&lt;</span></div>"#
+        );
+    }
+}
@@ -0,0 +1,526 @@
+use maud::{html, Markup, Render};
+
+use crate::{
+    attributes::{is_reserved, tag, Attributes},
+    form::FormState,
+    htmx::Htmx,
+    layout::join_class,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ButtonVariant {
+    #[default]
+    Primary,
+    Secondary,
+    Danger,
+    Link,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ButtonSize {
+    Small,
+    #[default]
+    Normal,
+    Large,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ButtonType {
+    #[default]
+    Submit,
+    Reset,
+    Button,
+}
+
+///Bouton de formulaire, ou lien présenté comme un bouton (annuler, retour)
+#[derive(Clone, Debug, Default)]
+pub struct Button {
+    label: String,
+    my_type: ButtonType,
+    href: Option<String>,
+    variant: ButtonVariant,
+    outline: bool,
+    size: ButtonSize,
+    icon: Option<Markup>,
+    icon_end: Option<Markup>,
+    id: Option<String>,
+    class: String,
+    name: Option<String>,
+    value: Option<String>,
+    formaction: Option<String>,
+    disabled: bool,
+    loading: bool,
+    attributes: Attributes,
+    htmx: Option<Htmx>,
+    state: FormState,
+}
+
+impl Button {
+    fn new(my_type: ButtonType, label: &str) -> Self {
+        Self {
+            label: label.to_owned(),
+            my_type,
+            ..Default::default()
+        }
+    }
+
+    pub fn submit(label: &str) -> Self {
+        Self::new(ButtonType::Submit, label)
+    }
+
+    pub fn reset(label: &str) -> Self {
+        Self::new(ButtonType::Reset, label).variant(ButtonVariant::Secondary)
+    }
+
+    ///Bouton sans action de formulaire (type=button)
+    pub fn plain(label: &str) -> Self {
+        Self::new(ButtonType::Button, label)
+    }
+
+    ///Lien (balise a) avec l'apparence d'un bouton
+    pub fn link(label: &str, href: &str) -> Self {
+        Self {
+            href: Some(href.to_owned()),
+            ..Self::new(ButtonType::Button, label).variant(ButtonVariant::Secondary)
+        }
+    }
+
+    pub fn variant(self, variant: ButtonVariant) -> Self {
+        Self { variant, ..self }
+    }
+
+    pub fn outline(self) -> Self {
+        Self {
+            outline: true,
+            ..self
+        }
+    }
+
+    pub fn size(self, size: ButtonSize) -> Self {
+        Self { size, ..self }
+    }
+
+    ///Icône placée avant le libellé
+    pub fn icon(self, icon: Markup) -> Self {
+        Self {
+            icon: Some(icon),
+            ..self
+        }
+    }
+
+    ///Icône placée après le libellé
+    pub fn icon_end(self, icon: Markup) -> Self {
+        Self {
+            icon_end: Some(icon),
+            ..self
+        }
+    }
+
+    pub fn id(self, id: &str) -> Self {
+        Self {
+            id: Some(id.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    ///name/value envoyés avec le formulaire quand ce bouton le soumet
+    pub fn name_value(self, name: &str, value: &str) -> Self {
+        Self {
+            name: Some(name.to_owned()),
+            value: Some(value.to_owned()),
+            ..self
+        }
+    }
+
+    ///Url de soumission propre à ce bouton
+    pub fn formaction(self, url: &str) -> Self {
+        Self {
+            formaction: Some(url.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn disabled(self, disabled: bool) -> Self {
+        Self { disabled, ..self }
+    }
+
+    ///Traitement en cours : spinner affiché et bouton désactivé
+    pub fn loading(self, loading: bool) -> Self {
+        Self { loading, ..self }
+    }
+
+    ///Attribut quelconque (data-*, aria-*...)
+    ///
+    ///Comme Props::attr, `id` revient à Button::id et `name`, `class`, `type` et `value`
+    ///sont ignorés (voir class et name_value).
+    pub fn attr<V: ToString>(self, name: &str, value: V) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "id" => self.id(&value.to_string()),
+            _ if is_reserved(name) => self,
+            _ => Self {
+                attributes: self.attributes.set(name, value),
+                ..self
+            },
+        }
+    }
+
+    ///Attribut booléen quelconque (formnovalidate...), id, name, class, type et value ignorés
+    pub fn flag(self, name: &str) -> Self {
+        match name.eq_ignore_ascii_case("id") || is_reserved(name) {
+            true => self,
+            false => Self {
+                attributes: self.attributes.flag(name, true),
+                ..self
+            },
+        }
+    }
+
+    pub fn htmx(self, htmx: Htmx) -> Self {
+        Self {
+            htmx: Some(htmx),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+}
+
+impl Render for Button {
+    fn render(&self) -> Markup {
+        let theme = self.state.theme.as_ref();
+        let class = join_class(
+            &theme.button_class(self.variant, self.outline, self.size),
+            &self.class,
+        );
+        let htmx = self
+            .htmx
+            .as_ref()
+            .map(|x| x.attributes(None))
+            .unwrap_or_default();
+        let disabled = self.disabled || self.loading;
+
+        let content = html!(
+            @if self.loading {
+                span class=[join_class(theme.spinner_class(), "")] aria-hidden="true" {}
+                " "
+            }
+            @if let Some(icon) = &self.icon {
+                (icon)
+                " "
+            }
+            (self.label)
+            @if let Some(icon) = &self.icon_end {
+                " "
+                (icon)
+            }
+        );
+
+        if let Some(href) = &self.href {
+            let class = match disabled {
+                true => join_class(
+                    class.as_deref().unwrap_or_default(),
+                    theme.disabled_link_class(),
+                ),
+                false => class,
+            };
+            let attributes = Attributes::new()
+                .set_opt("href", (!disabled).then_some(href))
+                .set_opt("class", class)
+                .set_opt("id", self.id.as_ref())
+                .set("role", "button")
+                .set_opt("aria-disabled", disabled.then_some("true"))
+                .extend(&htmx)
+                .extend(&self.attributes);
+
+            return tag("a", &attributes, content);
+        }
+
+        let type_str = match self.my_type {
+            ButtonType::Submit => "submit",
+            ButtonType::Reset => "reset",
+            ButtonType::Button => "button",
+        };
+        let attributes = Attributes::new()
+            .set("type", type_str)
+            .set_opt("class", class)
+            .set_opt("id", self.id.as_ref())
+            .set_opt("name", self.name.as_ref())
+            .set_opt("value", self.value.as_ref())
+            .set_opt("formaction", self.formaction.as_ref())
+            .flag("disabled", disabled)
+            .set_opt("aria-busy", self.loading.then_some("true"))
+            .extend(&htmx)
+            .extend(&self.attributes);
+
+        tag("button", &attributes, content)
+    }
+}
+
+///Groupe de boutons accolés, ou barre d'outils contenant des groupes
+#[derive(Clone, Debug, Default)]
+pub struct ButtonGroup {
+    toolbar: bool,
+    vertical: bool,
+    label: Option<String>,
+    class: String,
+    items: Vec<Markup>,
+    state: FormState,
+}
+
+impl ButtonGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toolbar() -> Self {
+        Self {
+            toolbar: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn vertical(self) -> Self {
+        Self {
+            vertical: true,
+            ..self
+        }
+    }
+
+    ///Libellé pour les lecteurs d'écran (aria-label)
+    pub fn label(self, label: &str) -> Self {
+        Self {
+            label: Some(label.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    pub fn push<R: Render>(self, item: R) -> Self {
+        let mut items = self.items;
+        items.push(item.render());
+
+        Self { items, ..self }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+}
+
+impl Render for ButtonGroup {
+    fn render(&self) -> Markup {
+        let theme = self.state.theme.as_ref();
+        let (class, role) = match self.toolbar {
+            true => (theme.button_toolbar_class(), "toolbar"),
+            false => (theme.button_group_class(self.vertical), "group"),
+        };
+
+        html!(
+            div class=[join_class(class, &self.class)] role=(role) aria-label=[&self.label] {
+                @for item in &self.items {
+                    (item)
+                }
+            }
+        )
+    }
+}
+
+///Bouton ouvrant un menu de liens
+#[derive(Clone, Debug, Default)]
+pub struct Dropdown {
+    button: Button,
+    items: Vec<(String, String)>,
+}
+
+impl Dropdown {
+    pub fn new(button: Button) -> Self {
+        Self {
+            button,
+            items: Vec::new(),
+        }
+    }
+
+    pub fn link(self, label: &str, href: &str) -> Self {
+        let mut items = self.items;
+        items.push((label.to_owned(), href.to_owned()));
+
+        Self { items, ..self }
+    }
+}
+
+impl Render for Dropdown {
+    fn render(&self) -> Markup {
+        let theme = self.button.state.theme.as_ref();
+        let button = Button {
+            my_type: ButtonType::Button,
+            href: None,
+            ..self.button.clone()
+        }
+        .class(theme.dropdown_toggle_class())
+        .attr("data-bs-toggle", "dropdown")
+        .attr("aria-expanded", "false");
+
+        html!(
+            div class=[join_class(theme.dropdown_class(), "")] {
+                (button)
+                ul class=[join_class(theme.dropdown_menu_class(), "")] {
+                    @for (label, href) in &self.items {
+                        li {
+                            a class=[join_class(theme.dropdown_item_class(), "")] href=(href) {(label)}
+                        }
+                    }
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maud::html;
+
+    use crate::theme::Tailwind;
+
+    use super::*;
+
+    #[test]
+    fn submit() {
+        let button = Button::submit("Enregistrer")
+            .size(ButtonSize::Large)
+            .name_value("action", "save")
+            .formaction("/clients/save")
+            .icon(html!(i class="bi bi-check" {}));
+
+        assert_eq!(
+            button.render().into_string(),
+            concat!(
+                r#"<button type="submit" class="btn btn-primary btn-lg" name="action" value="save" formaction="/clients/save">"#,
+                r#"<i class="bi bi-check"></i> Enregistrer"#,
+                r#"</button>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn reserved_attributes() {
+        let button = Button::submit("Enregistrer")
+            .name_value("action", "save")
+            .attr("ID", "save")
+            .attr("type", "button")
+            .attr("name", "autre")
+            .attr("value", "x")
+            .attr("class", "x")
+            .attr("data-row", 3);
+
+        assert_eq!(
+            button.render().into_string(),
+            r#"<button type="submit" class="btn btn-primary" id="save" name="action" value="save" data-row="3">Enregistrer</button>"#
+        );
+    }
+
+    #[test]
+    fn loading() {
+        let button = Button::plain("Envoyer")
+            .variant(ButtonVariant::Danger)
+            .outline()
+            .loading(true);
+
+        assert_eq!(
+            button.render().into_string(),
+            concat!(
+                r#"<button type="button" class="btn btn-outline-danger" disabled aria-busy="true">"#,
+                r#"<span class="spinner-border spinner-border-sm" aria-hidden="true"></span> Envoyer"#,
+                r#"</button>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn link() {
+        assert_eq!(
+            Button::link("Annuler", "/clients").render().into_string(),
+            r#"<a href="/clients" class="btn btn-secondary" role="button">Annuler</a>"#
+        );
+        assert_eq!(
+            Button::link("Annuler", "/clients")
+                .disabled(true)
+                .render()
+                .into_string(),
+            r#"<a class="btn btn-secondary disabled" role="button" aria-disabled="true">Annuler</a>"#
+        );
+    }
+
+    #[test]
+    fn group() {
+        let footer = ButtonGroup::toolbar().label("Actions").push(
+            ButtonGroup::new()
+                .push(Button::submit("Enregistrer"))
+                .push(Button::reset("Effacer")),
+        );
+
+        assert_eq!(
+            footer.render().into_string(),
+            concat!(
+                r#"<div class="btn-toolbar" role="toolbar" aria-label="Actions">"#,
+                r#"<div class="btn-group" role="group">"#,
+                r#"<button type="submit" class="btn btn-primary">Enregistrer</button>"#,
+                r#"<button type="reset" class="btn btn-secondary">Effacer</button>"#,
+                r#"</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn dropdown() {
+        let dropdown = Dropdown::new(Button::plain("Exporter").variant(ButtonVariant::Secondary))
+            .link("CSV", "/export.csv")
+            .link("PDF", "/export.pdf");
+
+        assert_eq!(
+            dropdown.render().into_string(),
+            concat!(
+                r#"<div class="dropdown">"#,
+                r#"<button type="button" class="btn btn-secondary dropdown-toggle" data-bs-toggle="dropdown" aria-expanded="false">Exporter</button>"#,
+                r#"<ul class="dropdown-menu">"#,
+                r#"<li><a class="dropdown-item" href="/export.csv">CSV</a></li>"#,
+                r#"<li><a class="dropdown-item" href="/export.pdf">PDF</a></li>"#,
+                r#"</ul>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn tailwind() {
+        let button = Button::submit("Ok")
+            .size(ButtonSize::Small)
+            .state(&FormState::new().theme(Tailwind));
+
+        assert_eq!(
+            button.render().into_string(),
+            r#"<button type="submit" class="inline-flex items-center gap-2 rounded-md font-semibold px-2 py-1 text-xs bg-indigo-600 text-white hover:bg-indigo-500">Ok</button>"#
+        );
+    }
+}
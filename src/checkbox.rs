@@ -1,4 +1,15 @@
 use maud::{html, Markup, Render};
+use validator::ValidationErrors;
+
+use crate::{
+    attributes::{void_tag, Attributes},
+    display::{display_field, DisplayMode},
+    field_props::Props,
+    form::FormState,
+    form_data::FormData,
+    htmx::{field_wrapper_id, htmx_attributes, Htmx},
+    layout::{join_class, Layout},
+};
 
 #[derive(Clone, Debug, Default)]
 pub enum CheckboxType {
@@ -16,6 +27,14 @@ pub struct Checkbox {
     class: String,
     my_type: CheckboxType,
     is_checked: bool,
+    ///Valeurs lues par bind (toutes celles d'un name répété), comparées à props.value au rendu
+    bound: Option<Vec<String>>,
+    props: Props,
+    error: Option<String>,
+    state: FormState,
+    layout: Option<Layout>,
+    htmx: Option<Htmx>,
+    display: Option<DisplayMode>,
 }
 
 impl Checkbox {
@@ -23,7 +42,6 @@ impl Checkbox {
         Self {
             name: name.to_owned(),
             label: label.to_owned(),
-            class: "form-check".to_owned(),
             my_type,
             ..Default::default()
         }
@@ -38,7 +56,7 @@ impl Checkbox {
     }
 
     pub fn class(self, classes: &str) -> Self {
-        let class = format!("{} {}", self.class, classes);
+        let class = join_class(&self.class, classes).unwrap_or_default();
 
         Self { class, ..self }
     }
@@ -46,6 +64,96 @@ impl Checkbox {
     pub fn checked(self, is_checked: bool) -> Self {
         Self { is_checked, ..self }
     }
+
+    pub fn props(self, props: Props) -> Self {
+        Self { props, ..self }
+    }
+
+    pub fn errors(self, validation: &ValidationErrors) -> Self {
+        Self {
+            error: validation
+                .errors()
+                .get(self.name.as_str())
+                .map(|_| "".to_string()),
+            ..self
+        }
+    }
+
+    ///Champ en erreur sans passer par ValidationErrors (lignes d'un Repeater)
+    pub fn invalid(self, invalid: bool) -> Self {
+        Self {
+            error: invalid.then(String::new),
+            ..self
+        }
+    }
+
+    ///Coché si data contient la valeur de la case (props.value), ou une valeur vraie à défaut
+    ///
+    ///Pour un groupe de cases de même name (`roles=a&roles=b`), toutes les valeurs comptent.
+    ///La comparaison est faite au rendu : props peut être appelé avant ou après bind.
+    pub fn bind(self, data: &FormData) -> Self {
+        Self {
+            bound: Some(
+                data.get_all(&self.name)
+                    .into_iter()
+                    .map(str::to_owned)
+                    .collect(),
+            ),
+            ..self
+        }
+    }
+
+    ///Attributs htmx posés sur l'input
+    pub fn htmx(self, htmx: Htmx) -> Self {
+        Self {
+            htmx: Some(htmx),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Disposition propre au champ, prioritaire sur celle du formulaire
+    pub fn layout(self, layout: Layout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    ///Mode d'affichage propre au champ, prioritaire sur celui du formulaire
+    pub fn display(self, display: DisplayMode) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
+}
+
+impl Checkbox {
+    ///État coché : d'après la valeur liée par bind, sinon d'après checked
+    fn is_checked(&self) -> bool {
+        match (&self.bound, &self.props.value) {
+            (None, _) => self.is_checked,
+            (Some(bound), Some(value)) => bound.contains(value),
+            (Some(bound), None) => bound
+                .iter()
+                .any(|x| matches!(x.as_str(), "true" | "on" | "1")),
+        }
+    }
+
+    ///Les boutons radio partagent le même name : la valeur complète l'id
+    fn id_name(&self) -> String {
+        match (&self.my_type, &self.props.value) {
+            (CheckboxType::Radio, Some(value)) => format!("{}-{value}", self.name),
+            _ => self.name.clone(),
+        }
+    }
 }
 
 impl Render for Checkbox {
@@ -55,16 +163,77 @@ impl Render for Checkbox {
             CheckboxType::Radio => "radio",
         };
 
-        html!(
-            div class=(self.class) {
-                input
-                    name=(self.name)
-                    class="form-check-input"
-                    type=(type_str)
-                    checked[self.is_checked];
-                label class="form-check-label" {(self.label)}
+        let has_error = self.error.is_some();
+        let theme = self.state.theme.as_ref();
+        let validation = self.state.validation_class(has_error);
+
+        let ids = self
+            .props
+            .ids(&self.id_name(), self.state.show_valid_feedback(has_error));
+
+        let layout = self.state.field_layout(self.layout);
+
+        let display = self.state.field_display(self.display);
+        if display != DisplayMode::Edit {
+            let text = match self.is_checked() {
+                true => "Oui",
+                false => "Non",
+            };
+            return display_field(
+                &self.state,
+                display,
+                layout,
+                &self.class,
+                &self.label,
+                &ids.id,
+                text,
+            );
+        }
+
+        let wrapper_id = field_wrapper_id(&self.htmx, &ids.id);
+        //l'id est porté par le div le plus externe
+        let check_id = match layout {
+            Layout::Floating | Layout::Stacked => wrapper_id.clone(),
+            _ => None,
+        };
+
+        let attributes = Attributes::new()
+            .set("name", &self.name)
+            .set_opt("class", join_class(theme.check_input_class(), validation))
+            .set("type", type_str)
+            .set("id", &ids.id)
+            .set_opt("value", self.props.value.as_ref())
+            .flag("checked", self.is_checked())
+            .flag("required", self.props.required)
+            .set_opt("aria-invalid", has_error.then_some("true"))
+            .set_opt("aria-describedby", ids.described_by())
+            .extend(&htmx_attributes(&self.htmx, &wrapper_id))
+            .extend(self.props.attributes());
+
+        let check = html!(
+            div id=[check_id] class=[join_class(theme.check_wrapper_class(), &self.class)] {
+                (void_tag("input", &attributes))
+                label for=(ids.id) class=[join_class(theme.check_label_class(), "")] {(self.label)}
+                @if let (Some(feedback), Some(id)) = (&self.props.valid_feedback, &ids.feedback) {
+                    div id=(id) class=[join_class(theme.valid_feedback_class(), "")] {(feedback)}
+                }
+                @if let (Some(hint), Some(id)) = (&self.props.hint, &ids.hint) {
+                    div id=(id) class=[join_class(theme.hint_class(), "")] {(hint)}
+                }
             }
-        )
+        );
+
+        match layout {
+            Layout::Horizontal { .. } => html!(
+                div id=[wrapper_id] class=[join_class(theme.wrapper_class(layout), "")] {
+                    div class=[theme.check_column_class(layout)] {(check)}
+                }
+            ),
+            Layout::Inline => html!(
+                div id=[wrapper_id] class=[join_class(theme.wrapper_class(layout), "")] {(check)}
+            ),
+            Layout::Floating | Layout::Stacked => check,
+        }
     }
 }
 
@@ -72,7 +241,12 @@ impl Render for Checkbox {
 mod tests {
     use maud::Render;
 
-    use crate::checkbox::Checkbox;
+    use std::collections::HashMap;
+
+    use crate::{
+        checkbox::Checkbox, display::DisplayMode, field_props::Props, form::FormState,
+        form_data::FormData, layout::Layout,
+    };
 
     #[test]
     fn checkbox_default() {
@@ -82,8 +256,8 @@ mod tests {
             on.render().into_string(),
             concat!(
                 r#"<div class="form-check">"#,
-                r#"<input name="cbx" class="form-check-input" type="checkbox">"#,
-                r#"<label class="form-check-label">Choisir</label>"#,
+                r#"<input name="cbx" class="form-check-input" type="checkbox" id="cbx">"#,
+                r#"<label for="cbx" class="form-check-label">Choisir</label>"#,
                 r#"</div>"#,
             )
         );
@@ -99,8 +273,132 @@ mod tests {
             on.render().into_string(),
             concat!(
                 r#"<div class="form-check my-class">"#,
-                r#"<input name="cbx" class="form-check-input" type="checkbox" checked>"#,
-                r#"<label class="form-check-label">Choisir</label>"#,
+                r#"<input name="cbx" class="form-check-input" type="checkbox" id="cbx" checked>"#,
+                r#"<label for="cbx" class="form-check-label">Choisir</label>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn checkbox_valid_after_submit() {
+        let state = FormState::new().submitted(true);
+        let on = Checkbox::check("cbx", "Choisir")
+            .props(Props::default().valid_feedback("Bien"))
+            .state(&state);
+
+        assert_eq!(
+            on.render().into_string(),
+            concat!(
+                r#"<div class="form-check">"#,
+                r#"<input name="cbx" class="form-check-input is-valid" type="checkbox" id="cbx" aria-describedby="cbx-feedback">"#,
+                r#"<label for="cbx" class="form-check-label">Choisir</label>"#,
+                r#"<div id="cbx-feedback" class="valid-feedback">Bien</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn checkbox_horizontal() {
+        let state = FormState::new().layout(Layout::horizontal(2, 10));
+        let on = Checkbox::check("cbx", "Choisir").state(&state);
+
+        assert_eq!(
+            on.render().into_string(),
+            concat!(
+                r#"<div class="row">"#,
+                r#"<div class="col-sm-10 offset-sm-2">"#,
+                r#"<div class="form-check">"#,
+                r#"<input name="cbx" class="form-check-input" type="checkbox" id="cbx">"#,
+                r#"<label for="cbx" class="form-check-label">Choisir</label>"#,
+                r#"</div>"#,
+                r#"</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn radio_ids() {
+        let oui = Checkbox::radio("choix", "Oui").props(Props::default().value("1"));
+        let non = Checkbox::radio("choix", "Non").props(Props::default().value("0"));
+
+        assert_eq!(
+            oui.render().into_string(),
+            concat!(
+                r#"<div class="form-check">"#,
+                r#"<input name="choix" class="form-check-input" type="radio" id="choix-1" value="1">"#,
+                r#"<label for="choix-1" class="form-check-label">Oui</label>"#,
+                r#"</div>"#,
+            )
+        );
+        assert!(non.render().into_string().contains(r#"id="choix-0""#));
+    }
+
+    #[test]
+    fn checkbox_bind() {
+        let data = FormData::from(HashMap::from([
+            ("actif".to_owned(), "true".to_owned()),
+            ("choix".to_owned(), "0".to_owned()),
+        ]));
+
+        assert!(Checkbox::check("actif", "Actif").bind(&data).is_checked());
+        assert!(!Checkbox::check("autre", "Autre").bind(&data).is_checked());
+        assert!(Checkbox::radio("choix", "Non")
+            .props(Props::default().value("0"))
+            .bind(&data)
+            .is_checked());
+        assert!(!Checkbox::radio("choix", "Oui")
+            .props(Props::default().value("1"))
+            .bind(&data)
+            .is_checked());
+        //bind avant props : la valeur de la case est connue au rendu
+        assert!(Checkbox::radio("choix", "Non")
+            .bind(&data)
+            .props(Props::default().value("0"))
+            .is_checked());
+        assert!(!Checkbox::radio("choix", "Oui")
+            .bind(&data)
+            .props(Props::default().value("1"))
+            .is_checked());
+    }
+
+    #[test]
+    fn checkbox_group() {
+        let data = FormData::from_pairs(&[("roles", "a"), ("roles", "b")]);
+        let role = |value: &str| {
+            Checkbox::check("roles", value)
+                .bind(&data)
+                .props(Props::default().value(value))
+                .is_checked()
+        };
+
+        assert!(role("a"));
+        assert!(role("b"));
+        assert!(!role("c"));
+    }
+
+    #[test]
+    fn checkbox_display() {
+        let state = FormState::new().display(DisplayMode::List);
+        let on = Checkbox::check("actif", "Actif")
+            .checked(true)
+            .state(&state);
+        let off = Checkbox::check("actif", "Actif")
+            .layout(Layout::Stacked)
+            .display(DisplayMode::Plaintext);
+
+        assert_eq!(
+            on.render().into_string(),
+            r#"<dt>Actif</dt><dd id="actif">Oui</dd>"#
+        );
+        assert_eq!(
+            off.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="actif" class="form-label">Actif</label>"#,
+                r#"<input type="text" class="form-control-plaintext" id="actif" value="Non" readonly>"#,
                 r#"</div>"#,
             )
         );
@@ -0,0 +1,519 @@
+use std::fmt;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use maud::{html, Markup, Render};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+///Nom du champ caché et du cookie portant le jeton
+pub const CSRF_FIELD: &str = "csrf_token";
+///En-tête accepté à la place du champ (htmx, fetch)
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+const NONCE_LEN: usize = 32;
+#[cfg(feature = "axum")]
+const BODY_LIMIT: usize = 2 * 1024 * 1024;
+const CSRF_CONTEXT: &str = "csrf";
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsrfError {
+    ///Cookie ou champ absent
+    Missing,
+    ///Jeton qui n'a pas le format nonce.signature
+    Malformed,
+    ///Jeton non signé par la clé du serveur
+    BadSignature,
+    ///Le champ soumis ne correspond pas au cookie
+    Mismatch,
+    ///Source aléatoire du système indisponible, aucun jeton ne peut être créé
+    Random,
+}
+
+impl fmt::Display for CsrfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Missing => "jeton CSRF absent",
+            Self::Malformed => "jeton CSRF mal formé",
+            Self::BadSignature => "signature du jeton CSRF invalide",
+            Self::Mismatch => "jeton CSRF différent du cookie",
+            Self::Random => "source aléatoire indisponible pour le jeton CSRF",
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for CsrfError {}
+
+///Jeton double-submit : posé en cookie et répété dans le formulaire
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsrfToken(String);
+
+impl CsrfToken {
+    ///Reprend le jeton d'un cookie déjà posé, après vérification de sa signature
+    pub fn from_cookie(key: &CsrfKey, cookie: &str) -> Result<Self, CsrfError> {
+        key.verify_token(cookie)?;
+
+        Ok(Self(cookie.to_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    ///Valeur de l'en-tête Set-Cookie
+    pub fn set_cookie(&self) -> String {
+        format!(
+            "{CSRF_FIELD}={}; Path=/; HttpOnly; Secure; SameSite=Strict",
+            self.0
+        )
+    }
+}
+
+impl fmt::Display for CsrfToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+///Clé secrète du serveur, signe (HMAC-SHA256) et vérifie les jetons
+#[derive(Clone)]
+pub struct CsrfKey {
+    secret: Vec<u8>,
+    #[cfg(feature = "axum")]
+    body_limit: usize,
+}
+
+impl fmt::Debug for CsrfKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsrfKey").finish_non_exhaustive()
+    }
+}
+
+impl CsrfKey {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            secret: secret.to_vec(),
+            #[cfg(feature = "axum")]
+            body_limit: BODY_LIMIT,
+        }
+    }
+
+    ///Taille maximale (2 Mo par défaut) du corps lu par verify_csrf pour y chercher le jeton
+    #[cfg(feature = "axum")]
+    pub fn body_limit(self, body_limit: usize) -> Self {
+        Self { body_limit, ..self }
+    }
+
+    pub fn generate(&self) -> Result<CsrfToken, CsrfError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut nonce).map_err(|_| CsrfError::Random)?;
+
+        Ok(CsrfToken(format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(nonce),
+            self.sign(CSRF_CONTEXT, &nonce)
+        )))
+    }
+
+    ///Jeton du cookie s'il est valide (voir cookie_value), sinon un nouveau jeton
+    ///
+    ///Réutiliser le cookie garde valides les formulaires ouverts dans d'autres onglets.
+    pub fn token(&self, cookie: Option<&str>) -> Result<CsrfToken, CsrfError> {
+        match cookie.map(|x| CsrfToken::from_cookie(self, x)) {
+            Some(Ok(token)) => Ok(token),
+            _ => self.generate(),
+        }
+    }
+
+    ///Vérifie la signature d'un jeton isolé
+    pub fn verify_token(&self, token: &str) -> Result<(), CsrfError> {
+        let (nonce, signature) = token.split_once('.').ok_or(CsrfError::Malformed)?;
+        let nonce = URL_SAFE_NO_PAD
+            .decode(nonce)
+            .map_err(|_| CsrfError::Malformed)?;
+
+        self.verify_signature(CSRF_CONTEXT, &nonce, signature)
+    }
+
+    ///Signature (base64url) d'un message quelconque, par exemple des valeurs cachées
+    ///
+    ///context sépare les usages de la clé : une signature ne vaut que pour son contexte.
+    pub fn sign(&self, context: &str, message: &[u8]) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(context, message).finalize().into_bytes())
+    }
+
+    pub fn verify_signature(
+        &self,
+        context: &str,
+        message: &[u8],
+        signature: &str,
+    ) -> Result<(), CsrfError> {
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| CsrfError::Malformed)?;
+
+        self.mac(context, message)
+            .verify_slice(&signature)
+            .map_err(|_| CsrfError::BadSignature)
+    }
+
+    ///Double-submit : le jeton du cookie est signé et identique à celui soumis
+    pub fn verify(&self, cookie: Option<&str>, submitted: Option<&str>) -> Result<(), CsrfError> {
+        let (cookie, submitted) = cookie.zip(submitted).ok_or(CsrfError::Missing)?;
+
+        self.verify_token(cookie)?;
+
+        //comparaison en temps constant, la durée ne renseigne pas sur le préfixe commun
+        match bool::from(cookie.as_bytes().ct_eq(submitted.as_bytes())) {
+            true => Ok(()),
+            false => Err(CsrfError::Mismatch),
+        }
+    }
+
+    fn mac(&self, context: &str, message: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("hmac accepte toute clé");
+        mac.update(context.as_bytes());
+        mac.update(&[0]);
+        mac.update(message);
+        mac
+    }
+}
+
+///Valeur d'un cookie dans l'en-tête Cookie
+pub fn cookie_value<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header
+        .split(';')
+        .filter_map(|x| x.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+///Champ caché portant le jeton
+#[derive(Clone, Debug)]
+pub struct CsrfField {
+    token: CsrfToken,
+}
+
+impl CsrfField {
+    pub fn new(token: &CsrfToken) -> Self {
+        Self {
+            token: token.clone(),
+        }
+    }
+}
+
+impl Render for CsrfField {
+    fn render(&self) -> Markup {
+        html!(input type="hidden" name=(CSRF_FIELD) value=(self.token);)
+    }
+}
+
+#[cfg(feature = "axum")]
+mod layer {
+    use axum::{
+        body::{to_bytes, Body, Bytes},
+        extract::{Request, State},
+        http::{header, Method, StatusCode},
+        middleware::Next,
+        response::{IntoResponse, Response},
+    };
+
+    use super::*;
+
+    impl IntoResponse for CsrfError {
+        fn into_response(self) -> Response {
+            let status = match self {
+                Self::Random => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::FORBIDDEN,
+            };
+
+            (status, self.to_string()).into_response()
+        }
+    }
+
+    ///Corps de formulaire dans lequel chercher le champ du jeton
+    enum FormBody {
+        Urlencoded,
+        Multipart { boundary: String },
+    }
+
+    impl FormBody {
+        fn from_content_type(content_type: &str) -> Option<Self> {
+            let mime = content_type.split(';').next().unwrap_or_default();
+
+            match mime.trim() {
+                "application/x-www-form-urlencoded" => Some(Self::Urlencoded),
+                "multipart/form-data" => multer::parse_boundary(content_type)
+                    .ok()
+                    .map(|boundary| Self::Multipart { boundary }),
+                _ => None,
+            }
+        }
+
+        async fn token(&self, bytes: &Bytes) -> Option<String> {
+            match self {
+                Self::Urlencoded => serde_urlencoded::from_bytes::<Vec<(String, String)>>(bytes)
+                    .ok()?
+                    .into_iter()
+                    .find(|(key, _)| key == CSRF_FIELD)
+                    .map(|(_, value)| value),
+                Self::Multipart { boundary } => {
+                    let stream = Body::from(bytes.clone()).into_data_stream();
+                    let mut multipart = multer::Multipart::new(stream, boundary);
+
+                    while let Ok(Some(field)) = multipart.next_field().await {
+                        if field.name() == Some(CSRF_FIELD) {
+                            return field.text().await.ok();
+                        }
+                    }
+
+                    None
+                }
+            }
+        }
+    }
+
+    ///Middleware axum vérifiant le jeton des requêtes POST, PUT, PATCH et DELETE
+    ///
+    ///`Router::new().layer(axum::middleware::from_fn_with_state(key, verify_csrf))`
+    ///Le jeton est lu dans l'en-tête x-csrf-token, sinon dans un corps urlencoded ou multipart.
+    ///Le corps est alors lu en entier, dans la limite de CsrfKey::body_limit (413 au-delà) :
+    ///pour de gros envois de fichiers, relever la limite ou passer le jeton dans l'en-tête,
+    ///le corps n'est alors pas lu.
+    pub async fn verify_csrf(State(key): State<CsrfKey>, req: Request, next: Next) -> Response {
+        if matches!(
+            *req.method(),
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+        ) {
+            return next.run(req).await;
+        }
+
+        let headers = req.headers();
+        let cookie = headers
+            .get(header::COOKIE)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| cookie_value(x, CSRF_FIELD))
+            .map(str::to_owned);
+        let in_header = headers
+            .get(CSRF_HEADER)
+            .and_then(|x| x.to_str().ok())
+            .map(str::to_owned);
+        let form = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|x| x.to_str().ok())
+            .and_then(FormBody::from_content_type);
+
+        let (req, submitted) = match (in_header, form) {
+            (Some(token), _) => (req, Some(token)),
+            (None, Some(form)) => {
+                let (parts, body) = req.into_parts();
+                let Ok(bytes) = to_bytes(body, key.body_limit).await else {
+                    return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+                };
+                let submitted = form.token(&bytes).await;
+
+                (Request::from_parts(parts, Body::from(bytes)), submitted)
+            }
+            (None, None) => (req, None),
+        };
+
+        match key.verify(cookie.as_deref(), submitted.as_deref()) {
+            Ok(()) => next.run(req).await,
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+pub use layer::verify_csrf;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_and_verify() {
+        let key = CsrfKey::new(b"secret");
+        let token = key.generate().unwrap();
+
+        assert_ne!(token, key.generate().unwrap());
+        assert_eq!(key.verify_token(token.as_str()), Ok(()));
+        assert_eq!(
+            key.verify(Some(token.as_str()), Some(token.as_str())),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let key = CsrfKey::new(b"secret");
+        let token = key.generate().unwrap();
+        let other = CsrfKey::new(b"autre").generate().unwrap();
+
+        assert_eq!(
+            key.verify(None, Some(token.as_str())),
+            Err(CsrfError::Missing)
+        );
+        assert_eq!(key.verify_token("abc"), Err(CsrfError::Malformed));
+        assert_eq!(
+            key.verify(Some(other.as_str()), Some(other.as_str())),
+            Err(CsrfError::BadSignature)
+        );
+        assert_eq!(
+            key.verify(Some(token.as_str()), Some(key.generate().unwrap().as_str())),
+            Err(CsrfError::Mismatch)
+        );
+    }
+
+    #[test]
+    fn reuse_cookie() {
+        let key = CsrfKey::new(b"secret");
+        let token = key.generate().unwrap();
+        let other = CsrfKey::new(b"autre").generate().unwrap();
+
+        assert_eq!(
+            CsrfToken::from_cookie(&key, token.as_str()),
+            Ok(token.clone())
+        );
+        assert_eq!(
+            CsrfToken::from_cookie(&key, other.as_str()),
+            Err(CsrfError::BadSignature)
+        );
+        assert_eq!(key.token(Some(token.as_str())), Ok(token.clone()));
+        assert_ne!(key.token(Some(other.as_str())).unwrap(), other);
+        assert_eq!(key.verify_token(key.token(None).unwrap().as_str()), Ok(()));
+    }
+
+    #[test]
+    fn signature() {
+        let key = CsrfKey::new(b"secret");
+        let signature = key.sign("wizard", b"nom=Dupont");
+
+        assert_eq!(
+            key.verify_signature("wizard", b"nom=Dupont", &signature),
+            Ok(())
+        );
+        assert_eq!(
+            key.verify_signature("wizard", b"nom=Durand", &signature),
+            Err(CsrfError::BadSignature)
+        );
+        assert_eq!(
+            key.verify_signature("csrf", b"nom=Dupont", &signature),
+            Err(CsrfError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn cookie() {
+        let header = "theme=dark; csrf_token=abc.def; autre=1";
+
+        assert_eq!(cookie_value(header, CSRF_FIELD), Some("abc.def"));
+        assert_eq!(cookie_value(header, "absent"), None);
+    }
+
+    #[test]
+    fn field() {
+        let token = CsrfToken("abc.def".to_owned());
+
+        assert_eq!(
+            CsrfField::new(&token).render().into_string(),
+            r#"<input type="hidden" name="csrf_token" value="abc.def">"#
+        );
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn layer() {
+        use axum::{
+            body::Body,
+            http::{header, Request, StatusCode},
+            middleware::from_fn_with_state,
+            routing::post,
+            Router,
+        };
+        use tower::ServiceExt;
+
+        let key = CsrfKey::new(b"secret");
+        let token = key.generate().unwrap();
+        let app = Router::new()
+            .route("/", post(|| async { "ok" }))
+            .layer(from_fn_with_state(key.clone(), verify_csrf));
+
+        let request = |body: String| {
+            Request::post("/")
+                .header(header::COOKIE, format!("{CSRF_FIELD}={token}"))
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let ok = app
+            .clone()
+            .oneshot(request(format!("nom=a&{CSRF_FIELD}={token}")))
+            .await
+            .unwrap();
+        let forbidden = app
+            .clone()
+            .oneshot(request("nom=a".to_owned()))
+            .await
+            .unwrap();
+
+        assert_eq!(ok.status(), StatusCode::OK);
+        assert_eq!(forbidden.status(), StatusCode::FORBIDDEN);
+
+        let multipart = |submitted: &str| {
+            Request::post("/")
+                .header(header::COOKIE, format!("{CSRF_FIELD}={token}"))
+                .header(
+                    header::CONTENT_TYPE,
+                    "multipart/form-data; boundary=\"XyZ\"",
+                )
+                .body(Body::from(format!(
+                    concat!(
+                        "--XyZ\r\n",
+                        "Content-Disposition: form-data; name=\"fichier\"; filename=\"a.txt\"\r\n",
+                        "Content-Type: text/plain\r\n\r\n",
+                        "name=\"{field}\"\r\n",
+                        "--XyZ\r\n",
+                        "Content-Disposition: form-data; name=\"{field}\"\r\n\r\n",
+                        "{submitted}\r\n",
+                        "--XyZ--\r\n",
+                    ),
+                    field = CSRF_FIELD,
+                    submitted = submitted,
+                )))
+                .unwrap()
+        };
+        let ok = app
+            .clone()
+            .oneshot(multipart(token.as_str()))
+            .await
+            .unwrap();
+        let forbidden = app.oneshot(multipart("abc.def")).await.unwrap();
+
+        assert_eq!(ok.status(), StatusCode::OK);
+        assert_eq!(forbidden.status(), StatusCode::FORBIDDEN);
+
+        //corps trop gros pour être lu, sauf si le jeton vient de l'en-tête
+        let app = Router::new()
+            .route("/", post(|| async { "ok" }))
+            .layer(from_fn_with_state(key.body_limit(16), verify_csrf));
+        let too_large = app
+            .clone()
+            .oneshot(multipart(token.as_str()))
+            .await
+            .unwrap();
+        let mut in_header = multipart("");
+        in_header
+            .headers_mut()
+            .insert(CSRF_HEADER, token.as_str().parse().unwrap());
+        let ok = app.oneshot(in_header).await.unwrap();
+
+        assert_eq!(too_large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(ok.status(), StatusCode::OK);
+    }
+}
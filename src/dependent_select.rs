@@ -0,0 +1,131 @@
+use maud::{html, Markup, Render};
+
+use crate::{
+    field_props::id_from_name,
+    htmx::{Htmx, Swap},
+    key_value::KeyValueInterface,
+    select::Select,
+};
+
+///Select enfant dont les options sont rechargées quand le select parent change (pays, région, ville)
+///
+///L'enfant envoie la valeur du parent à endpoint (`?pays=fr`), qui répond avec child_options.
+///Un enfant rechargé déclenche à son tour le rechargement de ses propres enfants.
+#[derive(Clone, Debug)]
+pub struct DependentSelect {
+    select: Select,
+    parent: String,
+    endpoint: String,
+}
+
+impl DependentSelect {
+    ///parent est le name du select parent, son id doit être celui déduit du name
+    pub fn new(select: Select, parent: &str, endpoint: &str) -> Self {
+        Self {
+            select,
+            parent: parent.to_owned(),
+            endpoint: endpoint.to_owned(),
+        }
+    }
+}
+
+impl Render for DependentSelect {
+    fn render(&self) -> Markup {
+        let parent_id = id_from_name(&self.parent);
+        let htmx = Htmx::new()
+            .get(&self.endpoint)
+            .trigger(&format!(
+                "change from:#{parent_id}, htmx:afterSettle from:#{parent_id}"
+            ))
+            .include(&format!("#{parent_id}"))
+            .target("this")
+            .swap(Swap::InnerHtml);
+
+        self.select.clone().htmx(htmx).render()
+    }
+}
+
+///Options du select enfant pour la clé du parent lue dans la query string
+///
+///Sans valeur du parent, seule l'option vide (placeholder) est rendue.
+pub fn child_options<A, F>(
+    query: &str,
+    parent: &str,
+    placeholder: Option<&str>,
+    lookup: F,
+) -> Markup
+where
+    A: KeyValueInterface,
+    F: FnOnce(&str) -> Vec<A>,
+{
+    let pairs = serde_urlencoded::from_str::<Vec<(String, String)>>(query).unwrap_or_default();
+    let parent_key = pairs
+        .iter()
+        .find(|(key, _)| key == parent)
+        .map(|(_, value)| value.as_str())
+        .filter(|x| !x.is_empty());
+    let items = parent_key.map(lookup).unwrap_or_default();
+
+    html!(
+        @if let Some(placeholder) = placeholder {
+            option value="" {(placeholder)}
+        }
+        @for item in items.iter().map(|x| x.to_kv()) {
+            option value=(item.key) {(item.value)}
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{field_props::Props, key_value::KeyValue, layout::Layout};
+
+    use super::*;
+
+    fn regions(pays: &str) -> Vec<KeyValue> {
+        match pays {
+            "fr" => vec![
+                KeyValue::new("ara", "Auvergne-Rhône-Alpes"),
+                KeyValue::new("bre", "Bretagne"),
+            ],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dependent() {
+        let region = Select::new_kv("region", "Région", &regions("fr"))
+            .props(Props::default().placeholder("Choisir"))
+            .layout(Layout::Stacked);
+        let select = DependentSelect::new(region, "pays", "/regions");
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="region" class="form-label">Région</label>"#,
+                r##"<select name="region" class="form-select" id="region" hx-get="/regions" hx-trigger="change from:#pays, htmx:afterSettle from:#pays" hx-include="#pays" hx-target="this" hx-swap="innerHTML">"##,
+                r#"<option value="">Choisir</option>"#,
+                r#"<option value="ara">Auvergne-Rhône-Alpes</option>"#,
+                r#"<option value="bre">Bretagne</option>"#,
+                r#"</select>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(
+            child_options("pays=fr", "pays", None, regions).into_string(),
+            concat!(
+                r#"<option value="ara">Auvergne-Rhône-Alpes</option>"#,
+                r#"<option value="bre">Bretagne</option>"#,
+            )
+        );
+        assert_eq!(
+            child_options("pays=", "pays", Some("Choisir"), regions).into_string(),
+            r#"<option value="">Choisir</option>"#
+        );
+    }
+}
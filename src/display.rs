@@ -0,0 +1,58 @@
+use maud::{html, Markup};
+
+use crate::{
+    attributes::{void_tag, Attributes},
+    form::FormState,
+    layout::{join_class, Layout},
+    theme::FieldParts,
+};
+
+///Rendu des champs : saisie, ou consultation (pages de détail)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisplayMode {
+    #[default]
+    Edit,
+    ///Texte statique dans un contrôle en lecture seule (form-control-plaintext)
+    Plaintext,
+    ///Couple dt/dd, à placer dans un dl
+    List,
+}
+
+///Champ en consultation, text est la valeur affichée
+pub(crate) fn display_field(
+    state: &FormState,
+    mode: DisplayMode,
+    layout: Layout,
+    class: &str,
+    label: &str,
+    id: &str,
+    text: &str,
+) -> Markup {
+    let theme = state.theme.as_ref();
+
+    match mode {
+        DisplayMode::List => html!(
+            dt class=[join_class("", class)] {(label)}
+            dd id=(id) {(text)}
+        ),
+        _ => {
+            let attributes = Attributes::new()
+                .set("type", "text")
+                .set_opt("class", join_class(theme.plaintext_class(), ""))
+                .set("id", id)
+                .set("value", text)
+                .flag("readonly", true);
+
+            theme.render_field(
+                layout,
+                FieldParts {
+                    class,
+                    label,
+                    for_id: id,
+                    control: void_tag("input", &attributes),
+                    ..Default::default()
+                },
+            )
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::attributes::{is_reserved, Attributes};
+
 #[derive(Clone, Debug, Default)]
 pub struct Props {
     pub value: Option<String>,
@@ -5,10 +7,52 @@ pub struct Props {
     pub hint: Option<String>,
     pub required: bool,
     pub placeholder: Option<String>,
+    pub valid_feedback: Option<String>,
+    ///Attributs supplémentaires du contrôle, rendus après les autres dans l'ordre d'ajout
+    attributes: Attributes,
 }
 
 pub type DynOptionalString = dyn Into<String>;
 
+///Ids d'un champ et des éléments qui le décrivent (hint, feedback)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldIds {
+    pub id: String,
+    pub hint: Option<String>,
+    pub feedback: Option<String>,
+}
+
+impl FieldIds {
+    ///Valeur de aria-describedby
+    pub fn described_by(&self) -> Option<String> {
+        let ids = [&self.hint, &self.feedback]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        (!ids.is_empty()).then(|| ids.join(" "))
+    }
+}
+
+///Id stable déduit du nom du champ : lines[0].qty donne lines-0-qty
+pub fn id_from_name(name: &str) -> String {
+    let id = name
+        .chars()
+        .map(
+            |c| match c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                true => c,
+                false => '-',
+            },
+        )
+        .collect::<String>();
+
+    id.split('-')
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
 impl Props {
     pub fn new_value<S: Into<String>>(value: Option<S>) -> Self {
         Self {
@@ -45,12 +89,91 @@ impl Props {
         }
     }
 
+    pub fn valid_feedback(self, value: &str) -> Self {
+        Self {
+            valid_feedback: Some(value.to_owned()),
+            ..self
+        }
+    }
+
     pub fn required(self) -> Self {
         Self {
             required: true,
             ..self
         }
     }
+
+    ///Attribut quelconque posé sur le contrôle, remplace celui rendu par le champ s'il existe
+    ///
+    ///`id` revient à Props::id (le label et aria-describedby suivent), `name`, `class`,
+    ///`type` et `value` restent ceux du champ et sont ignorés, comme un nom d'attribut invalide.
+    pub fn attr<V: ToString>(self, name: &str, value: V) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "id" => self.id(&value.to_string()),
+            _ if is_reserved(name) => self,
+            _ => Self {
+                attributes: self.attributes.set(name, value),
+                ..self
+            },
+        }
+    }
+
+    ///Attribut booléen quelconque (id, name, class, type et value ignorés)
+    pub fn flag(self, name: &str) -> Self {
+        match name.eq_ignore_ascii_case("id") || is_reserved(name) {
+            true => self,
+            false => Self {
+                attributes: self.attributes.flag(name, true),
+                ..self
+            },
+        }
+    }
+
+    ///Attributs supplémentaires posés par attr et flag
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn autocomplete(self, value: &str) -> Self {
+        self.attr("autocomplete", value)
+    }
+
+    pub fn autofocus(self) -> Self {
+        self.flag("autofocus")
+    }
+
+    pub fn readonly(self) -> Self {
+        self.flag("readonly")
+    }
+
+    pub fn disabled(self) -> Self {
+        self.flag("disabled")
+    }
+
+    pub fn tabindex(self, index: i32) -> Self {
+        self.attr("tabindex", index)
+    }
+
+    ///Attribut data-name
+    pub fn data(self, name: &str, value: &str) -> Self {
+        self.attr(&format!("data-{name}"), value)
+    }
+
+    pub fn style(self, value: &str) -> Self {
+        self.attr("style", value)
+    }
+
+    ///Id explicite si renseigné, sinon dérivé du nom. show_feedback indique si le valid-feedback est affiché
+    pub fn ids(&self, name: &str, show_feedback: bool) -> FieldIds {
+        let id = self.id.clone().unwrap_or_else(|| id_from_name(name));
+
+        FieldIds {
+            hint: self.hint.as_ref().map(|_| format!("{id}-hint")),
+            feedback: (show_feedback && self.valid_feedback.is_some())
+                .then(|| format!("{id}-feedback")),
+            id,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -76,4 +199,71 @@ mod tests {
         };
         assert_eq!(default.value, p.value);
     }
+
+    #[test]
+    fn id_from_nested_name() {
+        assert_eq!(id_from_name("name"), "name");
+        assert_eq!(id_from_name("lines[0].qty"), "lines-0-qty");
+    }
+
+    #[test]
+    fn ids() {
+        let ids = Props::default()
+            .hint("indice")
+            .valid_feedback("ok")
+            .ids("code", true);
+
+        assert_eq!(ids.id, "code");
+        assert_eq!(
+            ids.described_by(),
+            Some("code-hint code-feedback".to_owned())
+        );
+
+        let ids = Props::default()
+            .id("my_id")
+            .valid_feedback("ok")
+            .ids("code", false);
+
+        assert_eq!(ids.id, "my_id");
+        assert_eq!(ids.described_by(), None);
+    }
+
+    #[test]
+    fn attributes() {
+        let props = Props::default()
+            .autocomplete("off")
+            .readonly()
+            .data("row", "3")
+            .tabindex(-1)
+            .style("width: 10em");
+
+        assert_eq!(
+            props.attributes().to_string(),
+            r#" autocomplete="off" readonly data-row="3" tabindex="-1" style="width: 10em""#
+        );
+    }
+
+    #[test]
+    fn reserved_attributes() {
+        let props = Props::default()
+            .hint("indice")
+            .attr("ID", "code-client")
+            .attr("name", "autre")
+            .attr("class", "x")
+            .attr("Type", "hidden")
+            .attr("value", "autre")
+            .flag("id")
+            .flag("type")
+            .data("x y", "1");
+
+        assert!(props.attributes().is_empty());
+        assert_eq!(
+            props.ids("code", false),
+            FieldIds {
+                id: "code-client".to_owned(),
+                hint: Some("code-client-hint".to_owned()),
+                feedback: None,
+            }
+        );
+    }
 }
@@ -0,0 +1,240 @@
+use maud::{html, Markup, Render};
+
+use crate::{field_props::id_from_name, form::FormState, layout::join_class};
+
+///Section d'un formulaire : légende, description et champs
+#[derive(Clone, Debug, Default)]
+pub struct Fieldset {
+    legend: String,
+    description: Option<String>,
+    id: Option<String>,
+    class: String,
+    disabled: bool,
+    ///Repliable, ouvert ou fermé au départ
+    collapse: Option<bool>,
+    items: Vec<Markup>,
+    state: FormState,
+}
+
+impl Fieldset {
+    pub fn new(legend: &str) -> Self {
+        Self {
+            legend: legend.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn description(self, description: &str) -> Self {
+        Self {
+            description: Some(description.to_owned()),
+            ..self
+        }
+    }
+
+    ///Id explicite, à donner quand deux sections décrites ou repliables ont la même légende
+    ///(l'id déduit de la légende serait le même)
+    pub fn id(self, id: &str) -> Self {
+        Self {
+            id: Some(id.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    ///Désactive tous les contrôles de la section
+    pub fn disabled(self, disabled: bool) -> Self {
+        Self { disabled, ..self }
+    }
+
+    ///Section repliable (collapse Bootstrap), ouverte au départ si open
+    ///
+    ///Avec un thème autre que Bootstrap5, la section reste ouverte et la légende simple.
+    pub fn collapse(self, open: bool) -> Self {
+        Self {
+            collapse: Some(open),
+            ..self
+        }
+    }
+
+    pub fn push<R: Render>(self, item: R) -> Self {
+        let mut items = self.items;
+        items.push(item.render());
+
+        Self { items, ..self }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Id déduit de la légende seule, stable quelles que soient les valeurs et erreurs des champs
+    ///
+    ///Une légende sans caractère ascii donne `fieldset-` suivi d'un hachage de la légende.
+    fn derived_id(&self) -> String {
+        match id_from_name(&self.legend) {
+            id if id.is_empty() => {
+                //FNV-1a : stable d'un rendu à l'autre, contrairement au hachage de std
+                let hash = self.legend.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+                    (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+                });
+
+                format!("fieldset-{hash:08x}")
+            }
+            id => id,
+        }
+    }
+}
+
+impl Render for Fieldset {
+    fn render(&self) -> Markup {
+        let theme = self.state.theme.as_ref();
+        let collapse = theme.collapse(self.collapse);
+        //l'id n'est nécessaire que pour relier description et contenu repliable
+        let id = self
+            .id
+            .clone()
+            .or_else(|| {
+                (self.description.is_some() || collapse.is_some()).then(|| self.derived_id())
+            })
+            .unwrap_or_default();
+        let description_id = self
+            .description
+            .as_ref()
+            .map(|_| format!("{id}-description"));
+        let body_id = format!("{id}-body");
+
+        let items = html!(
+            @for item in &self.items {
+                (item)
+            }
+        );
+
+        html!(
+            fieldset
+                id=[self.id.as_ref()]
+                class=[join_class("", &self.class)]
+                disabled[self.disabled]
+                aria-describedby=[&description_id] {
+                legend class=[join_class(theme.legend_class(), "")] {
+                    @if let Some(open) = collapse {
+                        button
+                            type="button"
+                            class=[join_class(theme.collapse_toggle_class(), "")]
+                            data-bs-toggle="collapse"
+                            data-bs-target={"#" (body_id)}
+                            aria-expanded=(open)
+                            aria-controls=(body_id) {(self.legend)}
+                    } @else {
+                        (self.legend)
+                    }
+                }
+                @if let (Some(description), Some(id)) = (&self.description, &description_id) {
+                    div id=(id) class=[join_class(theme.hint_class(), "")] {(description)}
+                }
+                @if let Some(open) = collapse {
+                    div id=(body_id) class=[join_class(theme.collapse_class(open), "")] {(items)}
+                } @else {
+                    (items)
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{field_props::Props, layout::Layout, text::TextField, theme::Tailwind};
+
+    use super::*;
+
+    #[test]
+    fn fieldset() {
+        let fieldset = Fieldset::new("Adresse")
+            .description("Adresse de livraison")
+            .disabled(true)
+            .push(TextField::text("ville", "Ville").layout(Layout::Stacked));
+
+        assert_eq!(
+            fieldset.render().into_string(),
+            concat!(
+                r#"<fieldset disabled aria-describedby="Adresse-description">"#,
+                r#"<legend class="fs-5">Adresse</legend>"#,
+                r#"<div id="Adresse-description" class="form-text">Adresse de livraison</div>"#,
+                r#"<div>"#,
+                r#"<label for="ville" class="form-label">Ville</label>"#,
+                r#"<input type="text" class="form-control" name="ville" id="ville">"#,
+                r#"</div>"#,
+                r#"</fieldset>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn collapse() {
+        let fieldset = Fieldset::new("Options avancées")
+            .id("options")
+            .collapse(false)
+            .push(html!(p { "..." }));
+
+        assert_eq!(
+            fieldset.render().into_string(),
+            concat!(
+                r#"<fieldset id="options">"#,
+                r#"<legend class="fs-5">"#,
+                r##"<button type="button" class="btn btn-link p-0 text-decoration-none" data-bs-toggle="collapse" data-bs-target="#options-body" aria-expanded="false" aria-controls="options-body">Options avancées</button>"##,
+                r#"</legend>"#,
+                r#"<div id="options-body" class="collapse">"#,
+                r#"<p>...</p>"#,
+                r#"</div>"#,
+                r#"</fieldset>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn derived_id() {
+        let fieldset = |legend: &str, value: &str| {
+            Fieldset::new(legend)
+                .collapse(true)
+                .push(TextField::text("ville", "Ville").props(Props::default().value(value)))
+                .derived_id()
+        };
+
+        assert_eq!(
+            fieldset("Adresse de livraison", "Lyon"),
+            "Adresse-de-livraison"
+        );
+        assert_eq!(fieldset("Adresse", "Lyon"), fieldset("Adresse", "Paris"));
+        assert!(fieldset("日本", "Lyon").starts_with("fieldset-"));
+        assert_eq!(fieldset("日本", "Lyon").len(), "fieldset-".len() + 8);
+        assert_eq!(fieldset("日本", "Lyon"), fieldset("日本", "Paris"));
+        assert_ne!(fieldset("日本", "Lyon"), fieldset("中国", "Lyon"));
+    }
+
+    #[test]
+    fn collapse_without_bootstrap() {
+        let fieldset = Fieldset::new("Options avancées")
+            .collapse(false)
+            .state(&FormState::new().theme(Tailwind))
+            .push(html!(p { "..." }));
+
+        assert_eq!(
+            fieldset.render().into_string(),
+            concat!(
+                r#"<fieldset>"#,
+                r#"<legend class="text-base font-semibold text-gray-900">Options avancées</legend>"#,
+                r#"<p>...</p>"#,
+                r#"</fieldset>"#,
+            )
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use maud::{html, Markup, Render};
+use validator::ValidationErrors;
+
+use crate::{
+    attributes::{void_tag, Attributes},
+    checkbox::Checkbox,
+    display::{display_field, DisplayMode},
+    field_props::Props,
+    form::FormState,
+    htmx::{field_wrapper_id, htmx_attributes, Htmx},
+    layout::{join_class, Layout},
+    theme::FieldParts,
+};
+
+///Fichier déjà enregistré, affiché sous le champ
+#[derive(Clone, Debug, Default)]
+pub struct ExistingFile {
+    pub name: String,
+    pub url: String,
+    ///Miniature (image)
+    pub thumbnail: Option<String>,
+}
+
+impl ExistingFile {
+    pub fn new(name: &str, url: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            url: url.to_owned(),
+            thumbnail: None,
+        }
+    }
+
+    pub fn thumbnail(self, url: &str) -> Self {
+        Self {
+            thumbnail: Some(url.to_owned()),
+            ..self
+        }
+    }
+}
+
+///Champ de téléversement, à ajouter par Form::file (ou avec Form::multipart)
+#[derive(Clone, Debug, Default)]
+pub struct FileField {
+    name: String,
+    label: String,
+    class: String,
+    props: Props,
+    error: Option<String>,
+    state: FormState,
+    layout: Option<Layout>,
+    htmx: Option<Htmx>,
+    display: Option<DisplayMode>,
+    accept: Vec<String>,
+    multiple: bool,
+    max_size: Option<u64>,
+    existing: Option<ExistingFile>,
+    removable: bool,
+}
+
+impl FileField {
+    pub fn new(name: &str, label: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            label: label.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn props(self, props: Props) -> Self {
+        Self { props, ..self }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    pub fn errors(self, validation: &ValidationErrors) -> Self {
+        Self {
+            error: validation
+                .errors()
+                .get(self.name.as_str())
+                .map(|_| "".to_string()),
+            ..self
+        }
+    }
+
+    ///Champ en erreur sans passer par ValidationErrors (lignes d'un Repeater)
+    pub fn invalid(self, invalid: bool) -> Self {
+        Self {
+            error: invalid.then(String::new),
+            ..self
+        }
+    }
+
+    ///Type MIME (`image/*`) ou extension (`.pdf`) accepté, cumulable
+    pub fn accept(self, filter: &str) -> Self {
+        let mut accept = self.accept;
+        accept.push(filter.to_owned());
+
+        Self { accept, ..self }
+    }
+
+    pub fn multiple(self) -> Self {
+        Self {
+            multiple: true,
+            ..self
+        }
+    }
+
+    ///Taille maximale en octets, indiquée sous le champ (la vérification reste côté serveur)
+    pub fn max_size(self, bytes: u64) -> Self {
+        Self {
+            max_size: Some(bytes),
+            ..self
+        }
+    }
+
+    pub fn existing(self, file: ExistingFile) -> Self {
+        Self {
+            existing: Some(file),
+            ..self
+        }
+    }
+
+    ///Case `{name}_remove` pour supprimer le fichier existant
+    pub fn removable(self) -> Self {
+        Self {
+            removable: true,
+            ..self
+        }
+    }
+
+    ///Attributs htmx posés sur l'input
+    pub fn htmx(self, htmx: Htmx) -> Self {
+        Self {
+            htmx: Some(htmx),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Disposition propre au champ, un label flottant n'est pas possible sur un fichier
+    pub fn layout(self, layout: Layout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    ///Mode d'affichage propre au champ, prioritaire sur celui du formulaire
+    pub fn display(self, display: DisplayMode) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
+}
+
+///Taille lisible : 512 octets, 200 Ko, 1,5 Mo
+pub fn format_size(bytes: u64) -> String {
+    let units = [(1 << 30, "Go"), (1 << 20, "Mo"), (1 << 10, "Ko")];
+
+    match units.iter().find(|(size, _)| bytes >= *size) {
+        Some((size, unit)) => {
+            let value = format!("{:.1}", bytes as f64 / *size as f64);
+            let value = value.trim_end_matches(".0").replace('.', ",");
+
+            format!("{value} {unit}")
+        }
+        None => format!("{bytes} octets"),
+    }
+}
+
+impl Render for FileField {
+    fn render(&self) -> Markup {
+        let has_error = self.error.is_some();
+        let theme = self.state.theme.as_ref();
+        let validation = self.state.validation_class(has_error);
+
+        let layout = match self.state.field_layout(self.layout) {
+            Layout::Floating => Layout::Stacked,
+            layout => layout,
+        };
+
+        let size_hint = self
+            .max_size
+            .map(|x| format!("Taille maximale : {}", format_size(x)));
+        let hint = match (&self.props.hint, size_hint) {
+            (Some(hint), Some(size)) => Some(format!("{hint}. {size}")),
+            (hint, size) => hint.clone().or(size),
+        };
+        let mut props = self.props.clone();
+        props.hint = hint;
+        let ids = props.ids(&self.name, self.state.show_valid_feedback(has_error));
+
+        let display = self.state.field_display(self.display);
+        if display != DisplayMode::Edit {
+            let text = self
+                .existing
+                .as_ref()
+                .map(|x| x.name.as_str())
+                .unwrap_or_default();
+            return display_field(
+                &self.state,
+                display,
+                layout,
+                &self.class,
+                &self.label,
+                &ids.id,
+                text,
+            );
+        }
+
+        let wrapper_id = field_wrapper_id(&self.htmx, &ids.id);
+
+        let accept = (!self.accept.is_empty()).then(|| self.accept.join(","));
+        let attributes = Attributes::new()
+            .set("type", "file")
+            .set_opt("class", join_class(theme.input_class(), validation))
+            .set("name", &self.name)
+            .set("id", &ids.id)
+            .set_opt("accept", accept)
+            .flag("multiple", self.multiple)
+            .flag("required", props.required)
+            .set_opt("aria-invalid", has_error.then_some("true"))
+            .set_opt("aria-describedby", ids.described_by())
+            .extend(&htmx_attributes(&self.htmx, &wrapper_id))
+            .extend(props.attributes());
+        let control = void_tag("input", &attributes);
+
+        let remove = Checkbox::check(
+            &format!("{}_remove", self.name),
+            "Supprimer le fichier actuel",
+        )
+        .state(&self.state.clone().submitted(false))
+        .layout(Layout::Stacked);
+
+        let after = html!(
+            @if let (Some(feedback), Some(id)) = (&props.valid_feedback, &ids.feedback) {
+                div id=(id) class=[join_class(theme.valid_feedback_class(), "")] {(feedback)}
+            }
+            @if let (Some(hint), Some(id)) = (&props.hint, &ids.hint) {
+                div id=(id) class=[join_class(theme.hint_class(), "")] {(hint)}
+            }
+            @if let Some(existing) = &self.existing {
+                div class=[join_class(theme.hint_class(), "")] {
+                    @if let Some(thumbnail) = &existing.thumbnail {
+                        a href=(existing.url) {
+                            img src=(thumbnail) alt=(existing.name);
+                        }
+                    }
+                    a href=(existing.url) {(existing.name)}
+                }
+                @if self.removable {
+                    (remove)
+                }
+            }
+        );
+
+        theme.render_field(
+            layout,
+            FieldParts {
+                class: &self.class,
+                wrapper_id,
+                label: &self.label,
+                for_id: &ids.id,
+                control,
+                after,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(format_size(512), "512 octets");
+        assert_eq!(format_size(200 * 1024), "200 Ko");
+        assert_eq!(format_size(3 * 1024 * 1024 / 2), "1,5 Mo");
+        assert_eq!(format_size(2 << 30), "2 Go");
+    }
+
+    #[test]
+    fn file_default() {
+        let file = FileField::new("cv", "CV");
+
+        assert_eq!(
+            file.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="cv" class="form-label">CV</label>"#,
+                r#"<input type="file" class="form-control" name="cv" id="cv">"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn file_existing() {
+        let file = FileField::new("photo", "Photo")
+            .accept("image/png")
+            .accept(".jpg")
+            .multiple()
+            .max_size(2 * 1024 * 1024)
+            .existing(ExistingFile::new("moi.png", "/files/moi.png").thumbnail("/thumbs/moi.png"))
+            .removable();
+
+        assert_eq!(
+            file.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="photo" class="form-label">Photo</label>"#,
+                r#"<input type="file" class="form-control" name="photo" id="photo" accept="image/png,.jpg" multiple aria-describedby="photo-hint">"#,
+                r#"<div id="photo-hint" class="form-text">Taille maximale : 2 Mo</div>"#,
+                r#"<div class="form-text">"#,
+                r#"<a href="/files/moi.png"><img src="/thumbs/moi.png" alt="moi.png"></a>"#,
+                r#"<a href="/files/moi.png">moi.png</a>"#,
+                r#"</div>"#,
+                r#"<div class="form-check">"#,
+                r#"<input name="photo_remove" class="form-check-input" type="checkbox" id="photo_remove">"#,
+                r#"<label for="photo_remove" class="form-check-label">Supprimer le fichier actuel</label>"#,
+                r#"</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn file_remove_submitted() {
+        let file = FileField::new("cv", "CV")
+            .existing(ExistingFile::new("cv.pdf", "/files/cv.pdf"))
+            .removable()
+            .state(&FormState::new().submitted(true));
+
+        assert_eq!(
+            file.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="cv" class="form-label">CV</label>"#,
+                r#"<input type="file" class="form-control is-valid" name="cv" id="cv">"#,
+                r#"<div class="form-text"><a href="/files/cv.pdf">cv.pdf</a></div>"#,
+                r#"<div class="form-check">"#,
+                r#"<input name="cv_remove" class="form-check-input" type="checkbox" id="cv_remove">"#,
+                r#"<label for="cv_remove" class="form-check-label">Supprimer le fichier actuel</label>"#,
+                r#"</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+}
@@ -0,0 +1,415 @@
+use maud::{html, Markup, Render};
+
+use crate::{
+    button::{Button, ButtonSize, ButtonVariant},
+    field_props::Props,
+    form::{Form, FormState},
+    form_data::FormData,
+    key_value::{KeyValue, KeyValueInterface},
+    layout::{join_class, Layout},
+    query::{QueryString, PAGE_PARAM},
+    select::Select,
+    text::TextField,
+};
+
+///Suffixe du paramètre de début d'une période (`{name}_from`)
+pub const FROM_SUFFIX: &str = "_from";
+///Suffixe du paramètre de fin d'une période (`{name}_to`)
+pub const TO_SUFFIX: &str = "_to";
+///Préfixe des ids des champs, pour ne pas heurter ceux de la page (`filter-{name}`)
+pub const ID_PREFIX: &str = "filter-";
+
+#[derive(Clone, Debug)]
+enum FilterKind {
+    Search,
+    Select(Vec<KeyValue>),
+    DateRange,
+}
+
+#[derive(Clone, Debug)]
+struct Filter {
+    name: String,
+    label: String,
+    kind: FilterKind,
+}
+
+impl Filter {
+    ///Paramètres de la query portés par le filtre, avec le libellé de leur pastille
+    fn params(&self) -> Vec<(String, String)> {
+        match self.kind {
+            FilterKind::DateRange => vec![
+                (
+                    format!("{}{FROM_SUFFIX}", self.name),
+                    format!("{} : depuis le", self.label),
+                ),
+                (
+                    format!("{}{TO_SUFFIX}", self.name),
+                    format!("{} : jusqu'au", self.label),
+                ),
+            ],
+            _ => vec![(self.name.clone(), format!("{} :", self.label))],
+        }
+    }
+
+    ///Texte affiché dans la pastille pour value (libellé de l'option d'un select)
+    fn display<'a>(&'a self, value: &'a str) -> &'a str {
+        match &self.kind {
+            FilterKind::Select(items) => items
+                .iter()
+                .find(|x| x.key == value)
+                .map(|x| x.value.as_str())
+                .unwrap_or(value),
+            _ => value,
+        }
+    }
+}
+
+///Formulaire de filtres d'une page de liste, en get sur la query string
+///
+///Les champs sont remplis depuis la query courante, les autres paramètres (tri) sont conservés
+///en champs cachés et la page repart à 1. Chaque filtre actif donne une pastille qui le retire.
+#[derive(Clone, Debug, Default)]
+pub struct FilterBar {
+    action: String,
+    filters: Vec<Filter>,
+    query: QueryString,
+    state: FormState,
+}
+
+impl FilterBar {
+    ///action vide : la page courante
+    pub fn new(action: &str) -> Self {
+        Self {
+            action: action.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    ///Recherche texte libre
+    pub fn search(self, name: &str, label: &str) -> Self {
+        self.filter(name, label, FilterKind::Search)
+    }
+
+    ///Choix dans une liste, l'option vide (libellé du filtre) ne filtre pas
+    pub fn select<A: KeyValueInterface>(self, name: &str, label: &str, items: &[A]) -> Self {
+        let items = items.iter().map(|x| x.to_kv()).collect();
+
+        self.filter(name, label, FilterKind::Select(items))
+    }
+
+    ///Période entre deux dates, paramètres `{name}_from` et `{name}_to`
+    pub fn date_range(self, name: &str, label: &str) -> Self {
+        self.filter(name, label, FilterKind::DateRange)
+    }
+
+    ///Query string courante
+    pub fn query(self, query: &str) -> Self {
+        Self {
+            query: QueryString::parse(query),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    fn filter(self, name: &str, label: &str, kind: FilterKind) -> Self {
+        let mut filters = self.filters;
+        filters.push(Filter {
+            name: name.to_owned(),
+            label: label.to_owned(),
+            kind,
+        });
+
+        Self { filters, ..self }
+    }
+
+    fn is_filter_param(&self, name: &str) -> bool {
+        self.filters
+            .iter()
+            .any(|x| x.params().iter().any(|(param, _)| param == name))
+    }
+
+    ///Filtres renseignés dans la query : (paramètre, libellé, valeur affichée)
+    ///
+    ///Un paramètre répété (`status=open&status=closed`) donne une entrée par valeur.
+    pub fn active(&self) -> Vec<(String, String, String)> {
+        self.active_values()
+            .into_iter()
+            .map(|(param, label, _, display)| (param, label, display))
+            .collect()
+    }
+
+    ///Filtres renseignés : (paramètre, libellé, valeur, valeur affichée)
+    fn active_values(&self) -> Vec<(String, String, &str, String)> {
+        self.filters
+            .iter()
+            .flat_map(|filter| {
+                filter.params().into_iter().flat_map(move |(param, label)| {
+                    self.query
+                        .get_all(&param)
+                        .into_iter()
+                        .filter(|x| !x.trim().is_empty())
+                        .map(|value| {
+                            let display = filter.display(value).to_owned();
+                            (param.clone(), label.clone(), value, display)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect()
+    }
+
+    ///Nombre de valeurs renseignées pour le paramètre name
+    fn active_count(&self, name: &str) -> usize {
+        self.query
+            .get_all(name)
+            .into_iter()
+            .filter(|x| !x.trim().is_empty())
+            .count()
+    }
+
+    ///Lien vers la liste sans la valeur value de name (ni la page)
+    fn remove_value_href(&self, name: &str, value: &str) -> String {
+        self.query
+            .clone()
+            .remove(PAGE_PARAM)
+            .remove_value(name, value)
+            .href()
+    }
+
+    ///Lien vers la liste sans les paramètres names (ni la page)
+    fn remove_href(&self, names: &[&str]) -> String {
+        names
+            .iter()
+            .fold(self.query.clone().remove(PAGE_PARAM), |query, name| {
+                query.remove(name)
+            })
+            .href()
+    }
+
+    fn field(&self, filter: &Filter, data: &FormData, state: &FormState) -> Markup {
+        let props = Props::default()
+            .id(&format!("{ID_PREFIX}{}", filter.name))
+            .placeholder(&filter.label);
+
+        match &filter.kind {
+            FilterKind::Search => TextField::text(&filter.name, &filter.label)
+                .props(props)
+                .bind(data)
+                .state(state)
+                .render(),
+            FilterKind::Select(items) => {
+                let select = Select::new_kv(&filter.name, &filter.label, items)
+                    .props(props)
+                    .bind(data)
+                    .state(state);
+
+                //une valeur répétée dans la query est une sélection multiple, comme ses pastilles
+                match self.active_count(&filter.name) > 1 {
+                    true => select.multiple().render(),
+                    false => select.render(),
+                }
+            }
+            FilterKind::DateRange => html!(
+                @for (param, label) in filter.params() {
+                    (TextField::date(&param, &label)
+                        .props(Props::default().id(&format!("{ID_PREFIX}{param}")))
+                        .bind(data)
+                        .state(state))
+                }
+            ),
+        }
+    }
+}
+
+impl Render for FilterBar {
+    fn render(&self) -> Markup {
+        let theme = self.state.theme.as_ref();
+        let state = self.state.clone().layout(Layout::Inline);
+        let data = FormData::from_pairs(self.query.pairs());
+
+        let kept = self
+            .query
+            .pairs()
+            .iter()
+            .filter(|(name, _)| name != PAGE_PARAM && !self.is_filter_param(name));
+        let fields = html!(
+            @for (name, value) in kept {
+                input type="hidden" name=(name) value=(value);
+            }
+            @for filter in &self.filters {
+                (self.field(filter, &data, &state))
+            }
+            div class=[join_class(theme.wrapper_class(Layout::Inline), "")] {
+                (Button::submit("Filtrer").state(&self.state))
+            }
+        );
+        let form = Form::get(&self.action)
+            .class(theme.filter_bar_class())
+            .push(fields);
+
+        let active = self.active_values();
+        let params = active
+            .iter()
+            .map(|(param, _, _, _)| param.as_str())
+            .collect::<Vec<_>>();
+        let chip_class = join_class(theme.filter_chip_class(), "");
+        let clear_class = theme.button_class(ButtonVariant::Link, false, ButtonSize::Small);
+
+        html!(
+            (form)
+            @if !active.is_empty() {
+                div class=[join_class(theme.filter_chips_class(), "")] {
+                    @for (param, label, value, display) in &active {
+                        a class=[&chip_class] href=(self.remove_value_href(param, value)) aria-label={"Retirer le filtre " (label) " " (display)} {
+                            (label) " " (display) " "
+                            span aria-hidden="true" {"×"}
+                        }
+                    }
+                    a class=[join_class(&clear_class, "")] href=(self.remove_href(&params)) {"Effacer les filtres"}
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_bar() -> FilterBar {
+        FilterBar::new("/clients")
+            .search("q", "Recherche")
+            .select(
+                "status",
+                "Statut",
+                &[
+                    KeyValue::new("open", "Ouvert"),
+                    KeyValue::new("closed", "Fermé"),
+                ],
+            )
+            .date_range("created", "Création")
+    }
+
+    #[test]
+    fn active() {
+        let filter_bar = filter_bar().query("q=&status=open&created_to=2024-06-30&sort=name");
+
+        assert_eq!(
+            filter_bar.active(),
+            vec![
+                (
+                    "status".to_owned(),
+                    "Statut :".to_owned(),
+                    "Ouvert".to_owned()
+                ),
+                (
+                    "created_to".to_owned(),
+                    "Création : jusqu'au".to_owned(),
+                    "2024-06-30".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_select() {
+        let filter_bar = filter_bar().query("status=open&status=closed&sort=name");
+
+        assert_eq!(
+            filter_bar.active(),
+            vec![
+                (
+                    "status".to_owned(),
+                    "Statut :".to_owned(),
+                    "Ouvert".to_owned()
+                ),
+                (
+                    "status".to_owned(),
+                    "Statut :".to_owned(),
+                    "Fermé".to_owned()
+                ),
+            ]
+        );
+
+        let html = filter_bar.render().into_string();
+        assert!(html
+            .contains(r#"<select name="status" class="form-select" id="filter-status" multiple>"#));
+        assert!(html.contains(r#"<option value="open" selected>Ouvert</option>"#));
+        assert!(html.contains(r#"<option value="closed" selected>Fermé</option>"#));
+        assert!(html.contains(
+            r#"href="?status=closed&amp;sort=name" aria-label="Retirer le filtre Statut : Ouvert""#
+        ));
+        assert!(html.contains(
+            r#"href="?status=open&amp;sort=name" aria-label="Retirer le filtre Statut : Fermé""#
+        ));
+        assert!(html.contains(r#"<a class="btn btn-link btn-sm" href="?sort=name">"#));
+    }
+
+    #[test]
+    fn filter_bar_empty() {
+        let html = filter_bar().render().into_string();
+
+        assert!(html.starts_with(
+            r#"<form action="/clients" method="get" class="row g-2 align-items-end mb-3">"#
+        ));
+        assert!(!html.contains("Effacer les filtres"));
+    }
+
+    #[test]
+    fn filter_bar_query() {
+        let filter_bar = FilterBar::new("")
+            .search("q", "Recherche")
+            .select("status", "Statut", &[KeyValue::new("open", "Ouvert")])
+            .query("sort=name&q=dupont&status=open&page=3");
+
+        assert_eq!(
+            filter_bar.render().into_string(),
+            concat!(
+                r#"<form action="" method="get" class="row g-2 align-items-end mb-3">"#,
+                r#"<input type="hidden" name="sort" value="name">"#,
+                r#"<div class="col-auto">"#,
+                r#"<label for="filter-q" class="visually-hidden">Recherche</label>"#,
+                r#"<input type="text" class="form-control" name="q" id="filter-q" value="dupont" placeholder="Recherche">"#,
+                r#"</div>"#,
+                r#"<div class="col-auto">"#,
+                r#"<label for="filter-status" class="visually-hidden">Statut</label>"#,
+                r#"<select name="status" class="form-select" id="filter-status">"#,
+                r#"<option value="">Statut</option>"#,
+                r#"<option value="open" selected>Ouvert</option>"#,
+                r#"</select>"#,
+                r#"</div>"#,
+                r#"<div class="col-auto">"#,
+                r#"<button type="submit" class="btn btn-primary">Filtrer</button>"#,
+                r#"</div>"#,
+                r#"</form>"#,
+                r#"<div class="d-flex flex-wrap gap-2 align-items-center mb-3">"#,
+                r#"<a class="badge rounded-pill text-bg-light border text-decoration-none" href="?sort=name&amp;status=open" aria-label="Retirer le filtre Recherche : dupont">Recherche : dupont <span aria-hidden="true">×</span></a>"#,
+                r#"<a class="badge rounded-pill text-bg-light border text-decoration-none" href="?sort=name&amp;q=dupont" aria-label="Retirer le filtre Statut : Ouvert">Statut : Ouvert <span aria-hidden="true">×</span></a>"#,
+                r#"<a class="btn btn-link btn-sm" href="?sort=name">Effacer les filtres</a>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn date_range() {
+        let html = filter_bar()
+            .query("created_from=2024-01-01")
+            .render()
+            .into_string();
+
+        assert!(html.contains(r#"<input type="date" class="form-control" name="created_from" id="filter-created_from" value="2024-01-01">"#));
+        assert!(html.contains(
+            r#"<input type="date" class="form-control" name="created_to" id="filter-created_to">"#
+        ));
+        assert!(html.contains(
+            r#"href="?" aria-label="Retirer le filtre Création : depuis le 2024-01-01""#
+        ));
+    }
+}
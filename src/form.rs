@@ -0,0 +1,296 @@
+use std::sync::Arc;
+
+use maud::{html, Markup, Render};
+
+use crate::{
+    attributes::{tag, Attributes},
+    display::DisplayMode,
+    file::FileField,
+    htmx::Htmx,
+    layout::{join_class, Layout},
+    theme::{default_theme, Theme},
+};
+
+///Paramètres communs à tous les champs d'un formulaire
+#[derive(Clone, Debug)]
+pub struct FormState {
+    pub submitted: bool,
+    pub layout: Layout,
+    pub theme: Arc<dyn Theme>,
+    pub display: DisplayMode,
+}
+
+impl Default for FormState {
+    fn default() -> Self {
+        Self {
+            submitted: false,
+            layout: Layout::default(),
+            theme: default_theme(),
+            display: DisplayMode::default(),
+        }
+    }
+}
+
+impl FormState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///Formulaire déjà soumis : les champs sans erreur passent en is-valid
+    pub fn submitted(self, submitted: bool) -> Self {
+        Self { submitted, ..self }
+    }
+
+    ///Disposition par défaut des champs (modifiable champ par champ)
+    pub fn layout(self, layout: Layout) -> Self {
+        Self { layout, ..self }
+    }
+
+    ///Thème utilisé pour le rendu, à la place de Bootstrap5
+    pub fn theme<T: Theme + 'static>(self, theme: T) -> Self {
+        Self {
+            theme: Arc::new(theme),
+            ..self
+        }
+    }
+
+    ///Formulaire en consultation : les champs sont affichés sans saisie possible
+    pub fn display(self, display: DisplayMode) -> Self {
+        Self { display, ..self }
+    }
+
+    ///Mode d'affichage effectif d'un champ
+    pub fn field_display(&self, display: Option<DisplayMode>) -> DisplayMode {
+        display.unwrap_or(self.display)
+    }
+
+    ///Disposition effective d'un champ, selon ce que permet le thème
+    pub fn field_layout(&self, layout: Option<Layout>) -> Layout {
+        self.theme.layout(layout.unwrap_or(self.layout))
+    }
+
+    ///Classe de validation à ajouter au contrôle
+    pub fn validation_class(&self, has_error: bool) -> &'static str {
+        match (has_error, self.submitted) {
+            (true, _) => self.theme.invalid_class(),
+            (false, true) => self.theme.valid_class(),
+            (false, false) => "",
+        }
+    }
+
+    ///Le message valid-feedback n'est affiché qu'après soumission, sur un champ sans erreur
+    pub fn show_valid_feedback(&self, has_error: bool) -> bool {
+        self.submitted && !has_error
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FormMethod {
+    Get,
+    #[default]
+    Post,
+}
+
+///Balise form contenant les champs
+#[derive(Clone, Debug, Default)]
+pub struct Form {
+    action: String,
+    method: FormMethod,
+    id: Option<String>,
+    class: String,
+    htmx: Option<Htmx>,
+    multipart: bool,
+    items: Vec<Markup>,
+}
+
+impl Form {
+    pub fn post(action: &str) -> Self {
+        Self {
+            action: action.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn get(action: &str) -> Self {
+        Self {
+            action: action.to_owned(),
+            method: FormMethod::Get,
+            ..Default::default()
+        }
+    }
+
+    pub fn id(self, id: &str) -> Self {
+        Self {
+            id: Some(id.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    pub fn htmx(self, htmx: Htmx) -> Self {
+        Self {
+            htmx: Some(htmx),
+            ..self
+        }
+    }
+
+    ///enctype multipart/form-data, nécessaire pour envoyer des fichiers
+    ///
+    ///La méthode n'est pas modifiée : un navigateur n'envoie les fichiers qu'en post.
+    pub fn multipart(self) -> Self {
+        Self {
+            multipart: true,
+            ..self
+        }
+    }
+
+    ///Élément rendu tel quel : un FileField placé dans un Fieldset demande Form::multipart
+    pub fn push<R: Render>(self, item: R) -> Self {
+        let mut items = self.items;
+        items.push(item.render());
+
+        Self { items, ..self }
+    }
+
+    ///Ajoute un champ fichier, le formulaire passe en multipart
+    pub fn file(self, field: FileField) -> Self {
+        self.multipart().push(field)
+    }
+}
+
+impl Render for Form {
+    fn render(&self) -> Markup {
+        let method = match self.method {
+            FormMethod::Get => "get",
+            FormMethod::Post => "post",
+        };
+
+        let attributes = Attributes::new()
+            .set("action", &self.action)
+            .set("method", method)
+            .set_opt("enctype", self.multipart.then_some("multipart/form-data"))
+            .set_opt("id", self.id.as_ref())
+            .set_opt("class", join_class(&self.class, ""))
+            .extend(
+                &self
+                    .htmx
+                    .as_ref()
+                    .map(|x| x.attributes(None))
+                    .unwrap_or_default(),
+            );
+
+        tag(
+            "form",
+            &attributes,
+            html!(
+                @for item in &self.items {
+                    (item)
+                }
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fieldset::Fieldset, file::FileField, htmx::Swap, text::TextField, theme::Plain};
+
+    use super::*;
+
+    #[test]
+    fn not_submitted() {
+        let state = FormState::new();
+
+        assert_eq!(state.validation_class(false), "");
+        assert_eq!(state.validation_class(true), "is-invalid");
+        assert!(!state.show_valid_feedback(false));
+    }
+
+    #[test]
+    fn submitted() {
+        let state = FormState::new().submitted(true);
+
+        assert_eq!(state.validation_class(false), "is-valid");
+        assert_eq!(state.validation_class(true), "is-invalid");
+        assert!(state.show_valid_feedback(false));
+        assert!(!state.show_valid_feedback(true));
+    }
+
+    #[test]
+    fn theme() {
+        let state = FormState::new().submitted(true).theme(Plain);
+
+        assert_eq!(state.validation_class(false), "valid");
+        assert_eq!(state.field_layout(None), Layout::Stacked);
+    }
+
+    #[test]
+    fn form() {
+        let form = Form::post("/clients")
+            .class("mb-3")
+            .htmx(
+                Htmx::new()
+                    .post("/clients")
+                    .target("#page")
+                    .swap(Swap::OuterHtml),
+            )
+            .push(TextField::text("nom", "Nom"));
+
+        assert_eq!(
+            form.render().into_string(),
+            concat!(
+                r##"<form action="/clients" method="post" class="mb-3" hx-post="/clients" hx-target="#page" hx-swap="outerHTML">"##,
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control" name="nom" id="nom">"#,
+                r#"<label for="nom">Nom</label>"#,
+                r#"</div>"#,
+                r#"</form>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn push_file() {
+        let start = |form: Form| {
+            let html = form.render().into_string();
+            html[..html.find('>').unwrap() + 1].to_owned()
+        };
+        let fieldset = Fieldset::new("Pièces jointes").push(FileField::new("cv", "CV"));
+
+        assert_eq!(
+            start(Form::post("/upload").push(FileField::new("cv", "CV"))),
+            r#"<form action="/upload" method="post">"#
+        );
+        assert_eq!(
+            start(Form::post("/upload").multipart().push(fieldset)),
+            r#"<form action="/upload" method="post" enctype="multipart/form-data">"#
+        );
+        assert_eq!(
+            start(Form::get("/upload").file(FileField::new("cv", "CV"))),
+            r#"<form action="/upload" method="get" enctype="multipart/form-data">"#
+        );
+    }
+
+    #[test]
+    fn form_multipart() {
+        let form = Form::post("/upload").file(FileField::new("cv", "CV"));
+
+        assert_eq!(
+            form.render().into_string(),
+            concat!(
+                r#"<form action="/upload" method="post" enctype="multipart/form-data">"#,
+                r#"<div>"#,
+                r#"<label for="cv" class="form-label">CV</label>"#,
+                r#"<input type="file" class="form-control" name="cv" id="cv">"#,
+                r#"</div>"#,
+                r#"</form>"#,
+            )
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+///Valeurs d'un formulaire indexées par nom de champ
+///
+///Les structures imbriquées donnent des noms `adresse.ville`, les tableaux `lignes[0].qte`.
+#[derive(Clone, Debug, Default)]
+pub struct FormData {
+    values: HashMap<String, String>,
+}
+
+impl FormData {
+    ///A partir d'un modèle (formulaire d'édition)
+    pub fn from_model<T: Serialize>(model: &T) -> Result<Self, serde_json::Error> {
+        let mut values = HashMap::new();
+        flatten("", &serde_json::to_value(model)?, &mut values);
+
+        Ok(Self { values })
+    }
+
+    ///A partir des valeurs soumises (formulaire en erreur)
+    pub fn from_submitted(values: HashMap<String, String>) -> Self {
+        Self { values }
+    }
+
+    ///A partir des couples nom/valeur d'un corps urlencoded, un name répété (select multiple)
+    ///est aussi indexé en `name[0]`, `name[1]`...
+    pub fn from_pairs<K, V>(pairs: &[(K, V)]) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut values = HashMap::new();
+        let mut counts = HashMap::<&str, usize>::new();
+
+        for (key, value) in pairs {
+            let count = counts.entry(key.as_ref()).or_default();
+            values
+                .entry(key.as_ref().to_owned())
+                .or_insert_with(|| value.as_ref().to_owned());
+            *count += 1;
+        }
+
+        for (key, _) in counts.iter().filter(|(_, count)| **count > 1) {
+            let repeated = pairs.iter().filter(|(k, _)| k.as_ref() == *key);
+
+            for (i, (_, value)) in repeated.enumerate() {
+                values.insert(format!("{key}[{i}]"), value.as_ref().to_owned());
+            }
+        }
+
+        Self { values }
+    }
+
+    ///Corps application/x-www-form-urlencoded tel que soumis
+    pub fn from_urlencoded(body: &str) -> Result<Self, serde_urlencoded::de::Error> {
+        let pairs = serde_urlencoded::from_str::<Vec<(String, String)>>(body)?;
+
+        Ok(Self::from_pairs(&pairs))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    ///Toutes les valeurs d'un champ multiple (`name[0]`, `name[1]`...), ou la valeur simple
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        let indexed = (0..)
+            .map_while(|i| self.get(&format!("{name}[{i}]")))
+            .collect::<Vec<_>>();
+
+        match indexed.is_empty() {
+            true => self.get(name).into_iter().collect(),
+            false => indexed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    ///Couples nom/valeur triés par nom
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        let mut entries = self
+            .values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        entries
+    }
+}
+
+impl From<HashMap<String, String>> for FormData {
+    fn from(values: HashMap<String, String>) -> Self {
+        Self::from_submitted(values)
+    }
+}
+
+fn flatten(prefix: &str, value: &Value, values: &mut HashMap<String, String>) {
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => {
+            values.insert(prefix.to_owned(), b.to_string());
+        }
+        Value::Number(n) => {
+            values.insert(prefix.to_owned(), n.to_string());
+        }
+        Value::String(s) => {
+            values.insert(prefix.to_owned(), s.clone());
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(&format!("{prefix}[{i}]"), item, values);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, item) in fields {
+                let name = match prefix {
+                    "" => key.clone(),
+                    _ => format!("{prefix}.{key}"),
+                };
+
+                flatten(&name, item, values);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Adresse {
+        ville: String,
+    }
+
+    #[derive(Serialize)]
+    struct Client {
+        nom: String,
+        age: u32,
+        actif: bool,
+        email: Option<String>,
+        adresse: Adresse,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn from_model() {
+        let client = Client {
+            nom: "Dupont".to_owned(),
+            age: 42,
+            actif: true,
+            email: None,
+            adresse: Adresse {
+                ville: "Lyon".to_owned(),
+            },
+            tags: vec!["a".to_owned(), "b".to_owned()],
+        };
+
+        let data = FormData::from_model(&client).unwrap();
+
+        assert_eq!(data.get("nom"), Some("Dupont"));
+        assert_eq!(data.get("age"), Some("42"));
+        assert_eq!(data.get("actif"), Some("true"));
+        assert_eq!(data.get("email"), None);
+        assert_eq!(data.get("adresse.ville"), Some("Lyon"));
+        assert_eq!(data.get("tags[1]"), Some("b"));
+        assert_eq!(data.get_all("tags"), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn from_pairs() {
+        let data = FormData::from_pairs(&[("nom", "Durand"), ("tags", "a"), ("tags", "c")]);
+
+        assert_eq!(data.get("nom"), Some("Durand"));
+        assert_eq!(data.get_all("nom"), vec!["Durand"]);
+        assert_eq!(data.get_all("tags"), vec!["a", "c"]);
+        assert!(data.get_all("absent").is_empty());
+    }
+
+    #[test]
+    fn from_submitted() {
+        let data = FormData::from(HashMap::from([("nom".to_owned(), "Durand".to_owned())]));
+
+        assert_eq!(data.get("nom"), Some("Durand"));
+        assert_eq!(data.get("prenom"), None);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::attributes::Attributes;
+
+///Valeurs de hx-swap
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Swap {
+    InnerHtml,
+    OuterHtml,
+    BeforeBegin,
+    AfterBegin,
+    BeforeEnd,
+    AfterEnd,
+    Delete,
+    None,
+}
+
+impl Swap {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InnerHtml => "innerHTML",
+            Self::OuterHtml => "outerHTML",
+            Self::BeforeBegin => "beforebegin",
+            Self::AfterBegin => "afterbegin",
+            Self::BeforeEnd => "beforeend",
+            Self::AfterEnd => "afterend",
+            Self::Delete => "delete",
+            Self::None => "none",
+        }
+    }
+}
+
+///Attributs htmx d'un champ ou d'un formulaire
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Htmx {
+    attributes: Attributes,
+    target_field: bool,
+}
+
+impl Htmx {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///Validation en ligne : le champ est posté à url à chaque modification,
+    ///la réponse (le champ ré-affiché) remplace le champ
+    pub fn validate(url: &str) -> Self {
+        Self::new()
+            .post(url)
+            .trigger("change")
+            .target_field()
+            .swap(Swap::OuterHtml)
+    }
+
+    pub fn get(self, url: &str) -> Self {
+        self.attr("hx-get", url)
+    }
+
+    pub fn post(self, url: &str) -> Self {
+        self.attr("hx-post", url)
+    }
+
+    pub fn put(self, url: &str) -> Self {
+        self.attr("hx-put", url)
+    }
+
+    pub fn patch(self, url: &str) -> Self {
+        self.attr("hx-patch", url)
+    }
+
+    pub fn delete(self, url: &str) -> Self {
+        self.attr("hx-delete", url)
+    }
+
+    ///Sélecteur css de l'élément à remplacer
+    pub fn target(self, selector: &str) -> Self {
+        Self {
+            target_field: false,
+            ..self.attr("hx-target", selector)
+        }
+    }
+
+    ///Cible le champ complet (div englobant le label, le contrôle et les messages)
+    pub fn target_field(self) -> Self {
+        Self {
+            target_field: true,
+            ..self
+        }
+    }
+
+    pub fn trigger(self, trigger: &str) -> Self {
+        self.attr("hx-trigger", trigger)
+    }
+
+    pub fn swap(self, swap: Swap) -> Self {
+        self.attr("hx-swap", swap.as_str())
+    }
+
+    pub fn select(self, selector: &str) -> Self {
+        self.attr("hx-select", selector)
+    }
+
+    pub fn include(self, selector: &str) -> Self {
+        self.attr("hx-include", selector)
+    }
+
+    pub fn indicator(self, selector: &str) -> Self {
+        self.attr("hx-indicator", selector)
+    }
+
+    pub fn push_url(self, push: bool) -> Self {
+        self.attr("hx-push-url", push)
+    }
+
+    pub fn confirm(self, message: &str) -> Self {
+        self.attr("hx-confirm", message)
+    }
+
+    ///Valeurs json ajoutées à la requête
+    pub fn vals(self, json: &str) -> Self {
+        self.attr("hx-vals", json)
+    }
+
+    ///Tout autre attribut htmx (hx-ext, hx-boost...)
+    pub fn attr<V: ToString>(self, name: &str, value: V) -> Self {
+        Self {
+            attributes: self.attributes.set(name, value),
+            ..self
+        }
+    }
+
+    pub fn targets_field(&self) -> bool {
+        self.target_field
+    }
+
+    ///Attributs à rendre, field_id est l'id du div englobant le champ ciblé par target_field
+    pub fn attributes(&self, field_id: Option<&str>) -> Attributes {
+        let target = match (self.target_field, field_id) {
+            (true, Some(id)) => Some(format!("#{id}")),
+            (true, None) => Some("this".to_owned()),
+            (false, _) => None,
+        };
+
+        self.attributes.clone().set_opt("hx-target", target)
+    }
+}
+
+///Id du div englobant un champ, quand htmx le cible
+pub(crate) fn field_wrapper_id(htmx: &Option<Htmx>, id: &str) -> Option<String> {
+    htmx.as_ref()
+        .filter(|x| x.targets_field())
+        .map(|_| format!("{id}-field"))
+}
+
+///Attributs htmx d'un champ, vide sans htmx
+pub(crate) fn htmx_attributes(htmx: &Option<Htmx>, wrapper_id: &Option<String>) -> Attributes {
+    htmx.as_ref()
+        .map(|x| x.attributes(wrapper_id.as_deref()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_order() {
+        let htmx = Htmx::new()
+            .get("/search")
+            .trigger("keyup changed delay:300ms")
+            .target("#results")
+            .swap(Swap::InnerHtml);
+
+        assert_eq!(
+            htmx.attributes(None).to_string(),
+            r##" hx-get="/search" hx-trigger="keyup changed delay:300ms" hx-target="#results" hx-swap="innerHTML""##
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let htmx = Htmx::validate("/validate");
+
+        assert_eq!(
+            htmx.attributes(Some("nom-field")).to_string(),
+            r##" hx-post="/validate" hx-trigger="change" hx-swap="outerHTML" hx-target="#nom-field""##
+        );
+        assert_eq!(
+            field_wrapper_id(&Some(htmx), "nom"),
+            Some("nom-field".to_owned())
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use maud::{Markup, Render};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
+
+use crate::{
+    checkbox::Checkbox,
+    form::FormState,
+    form_data::FormData,
+    range::RangeField,
+    schema::{parse_urlencoded, SchemaError},
+    select::Select,
+    text::TextField,
+};
+
+///Champ pouvant être ré-affiché seul avec les valeurs et erreurs d'une soumission
+pub trait FieldWidget: Render + Sized {
+    fn bind(self, data: &FormData) -> Self;
+    fn errors(self, errors: &ValidationErrors) -> Self;
+    fn state(self, state: &FormState) -> Self;
+}
+
+impl FieldWidget for TextField {
+    fn bind(self, data: &FormData) -> Self {
+        TextField::bind(self, data)
+    }
+
+    fn errors(self, errors: &ValidationErrors) -> Self {
+        TextField::errors(self, errors)
+    }
+
+    fn state(self, state: &FormState) -> Self {
+        TextField::state(self, state)
+    }
+}
+
+impl FieldWidget for Select {
+    fn bind(self, data: &FormData) -> Self {
+        Select::bind(self, data)
+    }
+
+    fn errors(self, errors: &ValidationErrors) -> Self {
+        Select::errors(self, errors)
+    }
+
+    fn state(self, state: &FormState) -> Self {
+        Select::state(self, state)
+    }
+}
+
+impl FieldWidget for Checkbox {
+    fn bind(self, data: &FormData) -> Self {
+        Checkbox::bind(self, data)
+    }
+
+    fn errors(self, errors: &ValidationErrors) -> Self {
+        Checkbox::errors(self, errors)
+    }
+
+    fn state(self, state: &FormState) -> Self {
+        Checkbox::state(self, state)
+    }
+}
+
+impl FieldWidget for RangeField {
+    fn bind(self, data: &FormData) -> Self {
+        RangeField::bind(self, data)
+    }
+
+    fn errors(self, errors: &ValidationErrors) -> Self {
+        RangeField::errors(self, errors)
+    }
+
+    fn state(self, state: &FormState) -> Self {
+        RangeField::state(self, state)
+    }
+}
+
+///Validation en ligne d'un seul champ (htmx, fetch)
+///
+///Le corps soumis est désérialisé en T puis validé, seules les erreurs du champ sont gardées.
+///Pour une soumission partielle, les autres champs de T doivent avoir `#[serde(default)]`
+///(ou le formulaire entier est envoyé avec `hx-include="closest form"`).
+///Une valeur du champ refusée par serde (texte dans un nombre) est une erreur du champ.
+#[derive(Clone, Debug)]
+pub struct FieldCheck {
+    data: FormData,
+    errors: ValidationErrors,
+}
+
+impl FieldCheck {
+    pub fn new<T>(body: &str, name: &str) -> Result<Self, SchemaError>
+    where
+        T: DeserializeOwned + Validate,
+    {
+        let data = FormData::from_urlencoded(body).map_err(SchemaError::Body)?;
+
+        match parse_urlencoded::<T>(body) {
+            Ok(value) => Ok(Self::validate(&value, data, name)),
+            //erreur de serde sur le champ vérifié, les autres champs empêchent toute vérification
+            Err(SchemaError::Invalid(errors)) if errors.errors().contains_key(name) => Ok(Self {
+                data,
+                errors: field_errors(&errors, name),
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
+    ///A partir d'une valeur déjà lue
+    pub fn validate<T: Validate>(value: &T, data: FormData, name: &str) -> Self {
+        let errors = match value.validate() {
+            Ok(()) => ValidationErrors::new(),
+            Err(errors) => field_errors(&errors, name),
+        };
+
+        Self { data, errors }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn data(&self) -> &FormData {
+        &self.data
+    }
+
+    pub fn errors(&self) -> &ValidationErrors {
+        &self.errors
+    }
+
+    ///Le champ ré-affiché avec la valeur saisie et son état (is-invalid ou is-valid)
+    pub fn render<W: FieldWidget>(&self, widget: W, state: &FormState) -> Markup {
+        widget
+            .bind(&self.data)
+            .errors(&self.errors)
+            .state(&state.clone().submitted(true))
+            .render()
+    }
+}
+
+///Ne garde que les erreurs du champ name
+pub fn field_errors(errors: &ValidationErrors, name: &str) -> ValidationErrors {
+    let mut result = ValidationErrors::new();
+
+    if let Some((key, ValidationErrorsKind::Field(list))) = errors.errors().get_key_value(name) {
+        for error in list {
+            result.add(key, error.clone());
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize, Validate)]
+    struct Client {
+        #[serde(default)]
+        #[validate(length(min = 2))]
+        nom: String,
+        #[serde(default)]
+        #[validate(email)]
+        email: String,
+        #[serde(default)]
+        #[validate(range(max = 150))]
+        age: u32,
+    }
+
+    #[test]
+    fn invalid_field() {
+        let check = FieldCheck::new::<Client>("nom=D&email=x", "nom").unwrap();
+
+        assert!(!check.is_valid());
+        assert!(!check.errors().errors().contains_key("email"));
+        assert_eq!(
+            check
+                .render(TextField::text("nom", "Nom"), &FormState::new())
+                .into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control is-invalid" name="nom" id="nom" value="D" aria-invalid="true">"#,
+                r#"<label for="nom">Nom</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn type_error() {
+        let check = FieldCheck::new::<Client>("nom=Dupont&age=abc", "age").unwrap();
+
+        assert_eq!(check.errors().field_errors()["age"][0].code, "invalid");
+        assert_eq!(
+            check
+                .render(TextField::number("age", "Âge"), &FormState::new())
+                .into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="number" class="form-control is-invalid" name="age" id="age" value="abc" aria-invalid="true">"#,
+                r#"<label for="age">Âge</label>"#,
+                r#"</div>"#
+            )
+        );
+        assert!(matches!(
+            FieldCheck::new::<Client>("nom=Dupont&age=abc", "nom"),
+            Err(SchemaError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn partial_submission() {
+        let check = FieldCheck::new::<Client>("email=a%40b.fr", "email").unwrap();
+
+        assert!(check.is_valid());
+        assert_eq!(
+            check
+                .render(TextField::email("email", "Email"), &FormState::new())
+                .into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="email" class="form-control is-valid" name="email" id="email" value="a@b.fr">"#,
+                r#"<label for="email">Email</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+}
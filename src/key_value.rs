@@ -28,6 +28,12 @@ impl KeyValue {
     }
 }
 
+impl KeyValueInterface for KeyValue {
+    fn to_kv(&self) -> KeyValue {
+        self.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,46 @@
+///Disposition du label par rapport au contrôle
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    ///Label flottant Bootstrap (form-floating)
+    #[default]
+    Floating,
+
+    ///Label au-dessus du contrôle
+    Stacked,
+
+    ///Label et contrôle sur la même ligne, tailles en colonnes (sur 12)
+    Horizontal { label: u8, control: u8 },
+
+    ///Formulaire en ligne, label masqué visuellement
+    Inline,
+}
+
+impl Layout {
+    pub fn horizontal(label: u8, control: u8) -> Self {
+        Self::Horizontal { label, control }
+    }
+}
+
+pub(crate) fn join_class(base: &str, extra: &str) -> Option<String> {
+    let class = [base, extra]
+        .iter()
+        .filter(|x| !x.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!class.is_empty()).then_some(class)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join() {
+        assert_eq!(join_class("row", "mb-3"), Some("row mb-3".to_owned()));
+        assert_eq!(join_class("", "mb-3"), Some("mb-3".to_owned()));
+        assert_eq!(join_class("row", ""), Some("row".to_owned()));
+        assert_eq!(join_class("", ""), None);
+    }
+}
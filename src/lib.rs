@@ -1,8 +1,33 @@
+pub mod addon;
+pub mod attributes;
+pub mod button;
 pub mod checkbox;
+pub mod csrf;
+pub mod dependent_select;
+pub mod display;
 //pub mod error;
 pub mod field;
 pub mod field_props;
+pub mod fieldset;
+pub mod file;
+pub mod filter_bar;
+pub mod form;
+pub mod form_data;
 //pub mod grid;
+pub mod htmx;
+pub mod inline;
 pub mod key_value;
+pub mod layout;
+pub mod pagination;
+pub mod query;
+pub mod range;
+pub mod remote_select;
+pub mod repeater;
+pub mod schema;
 pub mod select;
+pub mod table;
 pub mod text;
+pub mod theme;
+#[cfg(feature = "axum")]
+pub mod validated_form;
+pub mod wizard;
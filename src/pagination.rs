@@ -0,0 +1,309 @@
+use std::{fmt, sync::Arc};
+
+use maud::{html, Markup, Render};
+
+use crate::{
+    form::FormState,
+    layout::join_class,
+    query::{QueryString, PAGE_PARAM},
+};
+
+const NEIGHBOURS: usize = 2;
+
+///Élément de la fenêtre de pages
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageItem {
+    Page(usize),
+    ///Pages omises entre deux liens
+    Ellipsis,
+}
+
+///Pages affichées : la première, la dernière et neighbours pages de part et d'autre de current
+///
+///Une seule page omise est affichée plutôt que remplacée par une ellipse.
+pub fn page_window(count: usize, current: usize, neighbours: usize) -> Vec<PageItem> {
+    let count = count.max(1);
+    let current = current.clamp(1, count);
+    let start = current.saturating_sub(neighbours).max(1);
+    let end = current.saturating_add(neighbours).min(count);
+
+    let mut pages = vec![1];
+    pages.extend(start..=end);
+    pages.push(count);
+    pages.dedup();
+
+    let mut items = Vec::new();
+    let mut previous = 0;
+    for page in pages {
+        match page - previous {
+            ..=1 => {}
+            2 => items.push(PageItem::Page(page - 1)),
+            _ => items.push(PageItem::Ellipsis),
+        }
+        items.push(PageItem::Page(page));
+        previous = page;
+    }
+
+    items
+}
+
+///Liens de pagination d'une liste, la page est portée par le paramètre `page` de la query
+#[derive(Clone)]
+pub struct Pagination {
+    total: usize,
+    per_page: usize,
+    page: usize,
+    neighbours: usize,
+    query: QueryString,
+    url: Option<Arc<dyn Fn(usize) -> String + Send + Sync>>,
+    label: String,
+    state: FormState,
+}
+
+impl fmt::Debug for Pagination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pagination")
+            .field("total", &self.total)
+            .field("per_page", &self.per_page)
+            .field("page", &self.page)
+            .field("neighbours", &self.neighbours)
+            .field("query", &self.query)
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Pagination {
+    ///Nombre total d'éléments, éléments par page et page courante (à partir de 1)
+    pub fn new(total: usize, per_page: usize, page: usize) -> Self {
+        Self {
+            total,
+            per_page: per_page.max(1),
+            page: page.max(1),
+            neighbours: NEIGHBOURS,
+            query: QueryString::default(),
+            url: None,
+            label: "Pagination".to_owned(),
+            state: FormState::default(),
+        }
+    }
+
+    ///Query string courante, conservée dans les liens (filtres, tri)
+    pub fn query(self, query: &str) -> Self {
+        self.query_string(QueryString::parse(query))
+    }
+
+    pub(crate) fn query_string(self, query: QueryString) -> Self {
+        Self { query, ..self }
+    }
+
+    ///Construction des liens à la place de la query string (`/clients/page/3`...)
+    pub fn url<F>(self, url: F) -> Self
+    where
+        F: Fn(usize) -> String + Send + Sync + 'static,
+    {
+        Self {
+            url: Some(Arc::new(url)),
+            ..self
+        }
+    }
+
+    ///Nombre de pages affichées de part et d'autre de la page courante (2 par défaut)
+    pub fn neighbours(self, neighbours: usize) -> Self {
+        Self { neighbours, ..self }
+    }
+
+    ///Nom accessible du nav
+    pub fn label(self, label: &str) -> Self {
+        Self {
+            label: label.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Nombre de pages, au moins 1
+    pub fn page_count(&self) -> usize {
+        self.total.div_ceil(self.per_page).max(1)
+    }
+
+    ///Page courante ramenée dans les pages existantes
+    pub fn page(&self) -> usize {
+        self.page.min(self.page_count())
+    }
+
+    ///Pages à afficher
+    pub fn window(&self) -> Vec<PageItem> {
+        page_window(self.page_count(), self.page(), self.neighbours)
+    }
+
+    fn href(&self, page: usize) -> String {
+        match (&self.url, page) {
+            (Some(url), page) => url(page),
+            (None, 1) => self.query.clone().remove(PAGE_PARAM).href(),
+            (None, page) => self.query.clone().set(PAGE_PARAM, page).href(),
+        }
+    }
+
+    fn item(&self, label: &str, page: Option<usize>, active: bool) -> Markup {
+        let theme = self.state.theme.as_ref();
+        let item_class = join_class(theme.page_item_class(active, page.is_none()), "");
+        let link_class = join_class(theme.page_link_class(), "");
+
+        html!(
+            li class=[item_class] {
+                @match page {
+                    Some(page) => a class=[link_class] href=(self.href(page)) aria-current=[active.then_some("page")] {(label)},
+                    None => span class=[link_class] {(label)},
+                }
+            }
+        )
+    }
+}
+
+impl Render for Pagination {
+    fn render(&self) -> Markup {
+        let count = self.page_count();
+        let page = self.page();
+        if count == 1 {
+            return html!();
+        }
+
+        let class = join_class(self.state.theme.pagination_class(), "");
+
+        html!(
+            nav aria-label=(self.label) {
+                ul class=[class] {
+                    (self.item("Précédent", (page > 1).then(|| page - 1), false))
+                    @for item in self.window() {
+                        @match item {
+                            PageItem::Page(i) => (self.item(&i.to_string(), Some(i), i == page)),
+                            PageItem::Ellipsis => (self.item("…", None, false)),
+                        }
+                    }
+                    (self.item("Suivant", (page < count).then(|| page + 1), false))
+                }
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pagination() {
+        let pagination = Pagination::new(25, 10, 1).query("q=a&page=1");
+
+        assert_eq!(
+            pagination.render().into_string(),
+            concat!(
+                r#"<nav aria-label="Pagination">"#,
+                r#"<ul class="pagination">"#,
+                r#"<li class="page-item disabled"><span class="page-link">Précédent</span></li>"#,
+                r#"<li class="page-item active"><a class="page-link" href="?q=a" aria-current="page">1</a></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="?q=a&amp;page=2">2</a></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="?q=a&amp;page=3">3</a></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="?q=a&amp;page=2">Suivant</a></li>"#,
+                r#"</ul>"#,
+                r#"</nav>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn single_page() {
+        assert_eq!(Pagination::new(10, 10, 1).render().into_string(), "");
+        assert_eq!(Pagination::new(0, 10, 3).page(), 1);
+    }
+
+    #[test]
+    fn page_count() {
+        assert_eq!(Pagination::new(0, 10, 1).page_count(), 1);
+        assert_eq!(Pagination::new(10, 10, 1).page_count(), 1);
+        assert_eq!(Pagination::new(11, 10, 1).page_count(), 2);
+        assert_eq!(Pagination::new(5, 0, 1).page_count(), 5);
+        assert_eq!(Pagination::new(95, 10, 42).page(), 10);
+        assert_eq!(Pagination::new(95, 10, 0).page(), 1);
+    }
+
+    fn window(count: usize, current: usize, neighbours: usize) -> String {
+        page_window(count, current, neighbours)
+            .iter()
+            .map(|x| match x {
+                PageItem::Page(i) => i.to_string(),
+                PageItem::Ellipsis => "…".to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn window_small() {
+        assert_eq!(window(0, 1, 2), "1");
+        assert_eq!(window(1, 1, 2), "1");
+        assert_eq!(window(2, 2, 2), "1 2");
+        assert_eq!(window(7, 1, 2), "1 2 3 … 7");
+        assert_eq!(window(7, 4, 2), "1 2 3 4 5 6 7");
+    }
+
+    #[test]
+    fn window_edges() {
+        assert_eq!(window(10, 1, 2), "1 2 3 … 10");
+        assert_eq!(window(10, 3, 2), "1 2 3 4 5 … 10");
+        assert_eq!(window(10, 10, 2), "1 … 8 9 10");
+        assert_eq!(window(10, 8, 2), "1 … 6 7 8 9 10");
+    }
+
+    #[test]
+    fn window_middle() {
+        assert_eq!(window(20, 10, 2), "1 … 8 9 10 11 12 … 20");
+        assert_eq!(window(20, 10, 0), "1 … 10 … 20");
+        assert_eq!(window(20, 10, 1), "1 … 9 10 11 … 20");
+    }
+
+    #[test]
+    fn window_single_gap() {
+        //une seule page omise est affichée plutôt qu'une ellipse
+        assert_eq!(window(10, 4, 1), "1 2 3 4 5 … 10");
+        assert_eq!(window(10, 7, 1), "1 … 6 7 8 9 10");
+        assert_eq!(window(10, 6, 2), "1 … 4 5 6 7 8 9 10");
+    }
+
+    #[test]
+    fn window_out_of_range() {
+        assert_eq!(window(10, 0, 2), "1 2 3 … 10");
+        assert_eq!(window(10, 99, 2), "1 … 8 9 10");
+        assert_eq!(window(5, 3, usize::MAX), "1 2 3 4 5");
+    }
+
+    #[test]
+    fn pagination_url() {
+        let pagination = Pagination::new(100, 10, 10)
+            .neighbours(1)
+            .url(|page| format!("/clients/page/{page}"));
+
+        assert_eq!(
+            pagination.render().into_string(),
+            concat!(
+                r#"<nav aria-label="Pagination">"#,
+                r#"<ul class="pagination">"#,
+                r#"<li class="page-item"><a class="page-link" href="/clients/page/9">Précédent</a></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="/clients/page/1">1</a></li>"#,
+                r#"<li class="page-item disabled"><span class="page-link">…</span></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="/clients/page/9">9</a></li>"#,
+                r#"<li class="page-item active"><a class="page-link" href="/clients/page/10" aria-current="page">10</a></li>"#,
+                r#"<li class="page-item disabled"><span class="page-link">Suivant</span></li>"#,
+                r#"</ul>"#,
+                r#"</nav>"#,
+            )
+        );
+    }
+}
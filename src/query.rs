@@ -0,0 +1,109 @@
+use std::fmt;
+
+///Paramètre portant la page demandée (à partir de 1)
+pub const PAGE_PARAM: &str = "page";
+
+///Query string d'une page de liste, modifiée pour construire les liens (tri, page, filtres)
+///
+///L'ordre des paramètres est conservé, un paramètre modifié passe en fin.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueryString {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryString {
+    ///Query string reçue, avec ou sans `?` ; une query invalide est vue comme vide
+    pub fn parse(query: &str) -> Self {
+        let query = query.strip_prefix('?').unwrap_or(query);
+
+        Self {
+            pairs: serde_urlencoded::from_str(query).unwrap_or_default(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.pairs
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    ///Remplace toutes les valeurs de name
+    pub fn set<V: ToString>(self, name: &str, value: V) -> Self {
+        let mut pairs = self.remove(name).pairs;
+        pairs.push((name.to_owned(), value.to_string()));
+
+        Self { pairs }
+    }
+
+    pub fn remove(self, name: &str) -> Self {
+        Self {
+            pairs: self
+                .pairs
+                .into_iter()
+                .filter(|(key, _)| key != name)
+                .collect(),
+        }
+    }
+
+    ///Retire une seule des valeurs de name
+    pub fn remove_value(self, name: &str, value: &str) -> Self {
+        Self {
+            pairs: self
+                .pairs
+                .into_iter()
+                .filter(|(key, x)| key != name || x != value)
+                .collect(),
+        }
+    }
+
+    ///Lien relatif vers la page courante avec cette query (`?` seul si elle est vide)
+    pub fn href(&self) -> String {
+        format!("?{self}")
+    }
+}
+
+impl fmt::Display for QueryString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_urlencoded::to_string(&self.pairs).map_err(|_| fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_string() {
+        let query = QueryString::parse("?q=caf%C3%A9&tag=a&page=3&tag=b");
+
+        assert_eq!(query.get("q"), Some("café"));
+        assert_eq!(query.get_all("tag"), vec!["a", "b"]);
+        assert_eq!(
+            query.clone().set("page", 4).href(),
+            "?q=caf%C3%A9&tag=a&tag=b&page=4"
+        );
+        assert_eq!(
+            query.clone().remove_value("tag", "a").to_string(),
+            "q=caf%C3%A9&page=3&tag=b"
+        );
+        assert_eq!(query.remove("tag").remove("q").to_string(), "page=3");
+        assert_eq!(QueryString::parse("").href(), "?");
+    }
+}
@@ -0,0 +1,359 @@
+use maud::{html, Markup, Render};
+use validator::ValidationErrors;
+
+use crate::{
+    attributes::{void_tag, Attributes},
+    display::{display_field, DisplayMode},
+    field_props::Props,
+    form::FormState,
+    form_data::FormData,
+    htmx::{field_wrapper_id, htmx_attributes, Htmx},
+    layout::{join_class, Layout},
+    theme::FieldParts,
+};
+
+///Curseur (input range) avec la valeur courante affichée dans un output
+#[derive(Clone, Debug, Default)]
+pub struct RangeField {
+    name: String,
+    label: String,
+    class: String,
+    props: Props,
+    ///Valeur reprise par bind, prioritaire sur props.value
+    bound: Option<String>,
+    error: Option<String>,
+    state: FormState,
+    layout: Option<Layout>,
+    htmx: Option<Htmx>,
+    display: Option<DisplayMode>,
+    min: Option<String>,
+    max: Option<String>,
+    step: Option<String>,
+    ticks: Vec<String>,
+}
+
+impl RangeField {
+    pub fn new(name: &str, label: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            label: label.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn props(self, props: Props) -> Self {
+        Self { props, ..self }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    pub fn errors(self, validation: &ValidationErrors) -> Self {
+        Self {
+            error: validation
+                .errors()
+                .get(self.name.as_str())
+                .map(|_| "".to_string()),
+            ..self
+        }
+    }
+
+    ///Champ en erreur sans passer par ValidationErrors (lignes d'un Repeater)
+    pub fn invalid(self, invalid: bool) -> Self {
+        Self {
+            error: invalid.then(String::new),
+            ..self
+        }
+    }
+
+    ///Valeur reprise de data d'après le name, prioritaire sur props.value quel que soit l'ordre des appels
+    pub fn bind(self, data: &FormData) -> Self {
+        Self {
+            bound: data.get(&self.name).map(str::to_owned).or(self.bound),
+            ..self
+        }
+    }
+
+    pub fn min<V: ToString>(self, min: V) -> Self {
+        Self {
+            min: Some(min.to_string()),
+            ..self
+        }
+    }
+
+    pub fn max<V: ToString>(self, max: V) -> Self {
+        Self {
+            max: Some(max.to_string()),
+            ..self
+        }
+    }
+
+    pub fn step<V: ToString>(self, step: V) -> Self {
+        Self {
+            step: Some(step.to_string()),
+            ..self
+        }
+    }
+
+    ///Graduations affichées sous le curseur (datalist)
+    pub fn ticks<V: ToString>(self, values: &[V]) -> Self {
+        Self {
+            ticks: values.iter().map(ToString::to_string).collect(),
+            ..self
+        }
+    }
+
+    ///Attributs htmx posés sur l'input
+    pub fn htmx(self, htmx: Htmx) -> Self {
+        Self {
+            htmx: Some(htmx),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Disposition propre au champ, un label flottant n'est pas possible sur un curseur
+    pub fn layout(self, layout: Layout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    ///Mode d'affichage propre au champ, prioritaire sur celui du formulaire
+    pub fn display(self, display: DisplayMode) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
+
+    ///Valeur affichée : celle reprise par bind, sinon props.value
+    fn value(&self) -> Option<&String> {
+        self.bound.as_ref().or(self.props.value.as_ref())
+    }
+
+    ///Valeur prise par le navigateur sans attribut value : le milieu entre min et max
+    ///(0 et 100 par défaut), ramené sur un pas, ou min si max est plus petit
+    fn default_value(&self) -> String {
+        let parse = |value: &Option<String>, default: f64| {
+            value
+                .as_deref()
+                .and_then(|x| x.parse::<f64>().ok())
+                .unwrap_or(default)
+        };
+        let min = parse(&self.min, 0.0);
+        let max = parse(&self.max, 100.0);
+        let step = parse(&self.step, 1.0);
+
+        let value = match (max < min, step > 0.0) {
+            (true, _) => min,
+            (false, true) => {
+                let value = min + ((max - min) / 2.0 / step).round() * step;
+                match value > max {
+                    true => value - step,
+                    false => value,
+                }
+            }
+            (false, false) => min + (max - min) / 2.0,
+        };
+
+        value.to_string()
+    }
+}
+
+impl Render for RangeField {
+    fn render(&self) -> Markup {
+        let has_error = self.error.is_some();
+        let theme = self.state.theme.as_ref();
+        let validation = self.state.validation_class(has_error);
+
+        let layout = match self.state.field_layout(self.layout) {
+            Layout::Floating => Layout::Stacked,
+            layout => layout,
+        };
+        let ids = self
+            .props
+            .ids(&self.name, self.state.show_valid_feedback(has_error));
+        let value = self
+            .value()
+            .cloned()
+            .unwrap_or_else(|| self.default_value());
+
+        let display = self.state.field_display(self.display);
+        if display != DisplayMode::Edit {
+            return display_field(
+                &self.state,
+                display,
+                layout,
+                &self.class,
+                &self.label,
+                &ids.id,
+                &value,
+            );
+        }
+
+        let wrapper_id = field_wrapper_id(&self.htmx, &ids.id);
+        let ticks_id = (!self.ticks.is_empty()).then(|| format!("{}-ticks", ids.id));
+
+        let attributes = Attributes::new()
+            .set("type", "range")
+            .set_opt("class", join_class(theme.range_class(), validation))
+            .set("name", &self.name)
+            .set("id", &ids.id)
+            .set_opt("value", self.value())
+            .set_opt("min", self.min.as_ref())
+            .set_opt("max", self.max.as_ref())
+            .set_opt("step", self.step.as_ref())
+            .set_opt("list", ticks_id.as_ref())
+            .flag("required", self.props.required)
+            .set_opt("aria-invalid", has_error.then_some("true"))
+            .set_opt("aria-describedby", ids.described_by())
+            .set("oninput", "this.nextElementSibling.value=this.value")
+            .extend(&htmx_attributes(&self.htmx, &wrapper_id))
+            .extend(self.props.attributes());
+
+        let control = html!(
+            (void_tag("input", &attributes))
+            output id={(ids.id) "-output"} for=(ids.id) {(value)}
+            @if let Some(ticks_id) = &ticks_id {
+                datalist id=(ticks_id) {
+                    @for tick in &self.ticks {
+                        option value=(tick) {}
+                    }
+                }
+            }
+        );
+
+        let after = html!(
+            @if let (Some(feedback), Some(id)) = (&self.props.valid_feedback, &ids.feedback) {
+                div id=(id) class=[join_class(theme.valid_feedback_class(), "")] {(feedback)}
+            }
+            @if let (Some(hint), Some(id)) = (&self.props.hint, &ids.hint) {
+                div id=(id) class=[join_class(theme.hint_class(), "")] {(hint)}
+            }
+        );
+
+        theme.render_field(
+            layout,
+            FieldParts {
+                class: &self.class,
+                wrapper_id,
+                label: &self.label,
+                for_id: &ids.id,
+                control,
+                after,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use validator::Validate;
+
+    use super::*;
+
+    #[derive(Validate)]
+    struct Note {
+        #[validate(range(max = 5))]
+        note: u8,
+    }
+
+    #[test]
+    fn range() {
+        let range = RangeField::new("volume", "Volume")
+            .props(Props::new_value(Some("30")).hint("En pourcentage"))
+            .min(0)
+            .max(100)
+            .step(10)
+            .ticks(&[0, 50, 100]);
+
+        assert_eq!(
+            range.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="volume" class="form-label">Volume</label>"#,
+                r#"<input type="range" class="form-range" name="volume" id="volume" value="30" min="0" max="100" step="10" list="volume-ticks" aria-describedby="volume-hint" oninput="this.nextElementSibling.value=this.value">"#,
+                r#"<output id="volume-output" for="volume">30</output>"#,
+                r#"<datalist id="volume-ticks">"#,
+                r#"<option value="0"></option>"#,
+                r#"<option value="50"></option>"#,
+                r#"<option value="100"></option>"#,
+                r#"</datalist>"#,
+                r#"<div id="volume-hint" class="form-text">En pourcentage</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn default_value() {
+        let output = |range: RangeField| {
+            let html = range.render().into_string();
+            let start = html.find("<output").unwrap();
+            html[start..html.find("</output>").unwrap()]
+                .rsplit('>')
+                .next()
+                .unwrap()
+                .to_owned()
+        };
+
+        assert_eq!(output(RangeField::new("v", "V")), "50");
+        assert_eq!(output(RangeField::new("v", "V").min(1).max(10)), "6");
+        assert_eq!(
+            output(RangeField::new("v", "V").min(0).max(1).step(0.25)),
+            "0.5"
+        );
+        assert_eq!(
+            output(RangeField::new("v", "V").min(0).max(10).step(4)),
+            "4"
+        );
+        assert_eq!(output(RangeField::new("v", "V").min(5).max(2)), "5");
+        assert_eq!(
+            RangeField::new("v", "V")
+                .props(Props::default().id("v'1"))
+                .render()
+                .into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="v'1" class="form-label">V</label>"#,
+                r#"<input type="range" class="form-range" name="v" id="v'1" oninput="this.nextElementSibling.value=this.value">"#,
+                r#"<output id="v'1-output" for="v'1">50</output>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn range_error() {
+        let errors = Note { note: 8 }.validate().unwrap_err();
+        let data = FormData::from_pairs(&[("note", "8")]);
+        let range = RangeField::new("note", "Note")
+            .max(10)
+            .bind(&data)
+            .errors(&errors)
+            .layout(Layout::Inline);
+
+        assert_eq!(
+            range.render().into_string(),
+            concat!(
+                r#"<div class="col-auto">"#,
+                r#"<label for="note" class="visually-hidden">Note</label>"#,
+                r#"<input type="range" class="form-range is-invalid" name="note" id="note" value="8" max="10" aria-invalid="true" oninput="this.nextElementSibling.value=this.value">"#,
+                r#"<output id="note-output" for="note">8</output>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+}
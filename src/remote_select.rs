@@ -0,0 +1,470 @@
+use maud::{html, Markup, Render};
+use validator::ValidationErrors;
+
+use crate::{
+    attributes::{void_tag, Attributes},
+    display::{display_field, DisplayMode},
+    field_props::Props,
+    form::FormState,
+    form_data::FormData,
+    key_value::{KeyValue, KeyValueInterface},
+    layout::{join_class, Layout},
+    query::QueryString,
+    theme::FieldParts,
+};
+
+///Paramètre portant le texte recherché
+pub const QUERY_PARAM: &str = "q";
+pub use crate::query::PAGE_PARAM;
+
+const PER_PAGE: usize = 20;
+
+//Comportements posés en hx-on : les ids passent par des attributs data-*, jamais dans le code.
+//La recherche vide la clé choisie, flèche bas entre dans la liste, Échap la ferme.
+const ON_INPUT: &str = "document.getElementById(this.dataset.valueInput).value=''";
+const ON_SEARCH_KEYDOWN: &str = "let l=document.getElementById(this.getAttribute('aria-controls'));if(event.key==='ArrowDown'){l.querySelector('[role=option]')?.focus();event.preventDefault()}else if(event.key==='Escape'){l.replaceChildren();this.setAttribute('aria-expanded','false')}";
+const ON_AFTER_REQUEST: &str = "this.setAttribute('aria-expanded',event.detail.successful)";
+//Dans la liste : flèches haut et bas entre les propositions, Échap revient à la recherche,
+//un clic (ou Entrée) recopie la clé et le libellé puis ferme la liste
+const ON_LIST_KEYDOWN: &str = "let o=[...this.querySelectorAll('[role=option]')],i=o.indexOf(document.activeElement),c=document.getElementById(this.dataset.input);if(event.key==='ArrowDown'&&i<o.length-1){o[i+1].focus();event.preventDefault()}else if(event.key==='ArrowUp'){(i>0?o[i-1]:c).focus();event.preventDefault()}else if(event.key==='Escape'){c.setAttribute('aria-expanded','false');c.focus();this.replaceChildren()}";
+const ON_PICK: &str = "let o=event.target.closest('[data-value]'),c=document.getElementById(this.dataset.input);if(o){document.getElementById(this.dataset.valueInput).value=o.dataset.value;c.value=o.dataset.label;c.setAttribute('aria-expanded','false');c.focus();this.replaceChildren()}";
+
+///Select dont les options sont cherchées sur le serveur (listes trop longues pour des option)
+///
+///Le texte saisi est envoyé à endpoint (`?q=...`), qui répond avec le fragment RemoteOptions.
+///La recherche n'a pas de name et n'est pas soumise avec le formulaire : seule la clé choisie
+///l'est, portée par un input caché nommé name. Le clavier (flèches, Entrée, Échap) et
+///aria-expanded sont gérés par des attributs hx-on.
+#[derive(Clone, Debug, Default)]
+pub struct RemoteSelect {
+    name: String,
+    label: String,
+    endpoint: String,
+    class: String,
+    props: Props,
+    ///Clé reprise par bind ou selected, prioritaire sur props.value
+    bound: Option<String>,
+    error: Option<String>,
+    state: FormState,
+    layout: Option<Layout>,
+    display: Option<DisplayMode>,
+    selected_label: Option<String>,
+}
+
+impl RemoteSelect {
+    pub fn new(name: &str, label: &str, endpoint: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            label: label.to_owned(),
+            endpoint: endpoint.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn props(self, props: Props) -> Self {
+        Self { props, ..self }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    pub fn errors(self, validation: &ValidationErrors) -> Self {
+        Self {
+            error: validation
+                .errors()
+                .get(self.name.as_str())
+                .map(|_| "".to_string()),
+            ..self
+        }
+    }
+
+    ///Champ en erreur sans passer par ValidationErrors (lignes d'un Repeater)
+    pub fn invalid(self, invalid: bool) -> Self {
+        Self {
+            error: invalid.then(String::new),
+            ..self
+        }
+    }
+
+    ///Élément choisi : la clé va dans l'input caché, le libellé dans la recherche
+    pub fn selected<A: KeyValueInterface>(self, item: &A) -> Self {
+        let kv = item.to_kv();
+
+        Self {
+            bound: Some(kv.key),
+            selected_label: Some(kv.value),
+            ..self
+        }
+    }
+
+    ///Clé reprise de data d'après le name, prioritaire sur props.value (le libellé est à donner par selected)
+    pub fn bind(self, data: &FormData) -> Self {
+        Self {
+            bound: data.get(&self.name).map(str::to_owned).or(self.bound),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Disposition propre au champ, prioritaire sur celle du formulaire
+    pub fn layout(self, layout: Layout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    ///Mode d'affichage propre au champ, prioritaire sur celui du formulaire
+    pub fn display(self, display: DisplayMode) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
+
+    ///Valeur affichée : celle reprise par bind, sinon props.value
+    fn value(&self) -> Option<&String> {
+        self.bound.as_ref().or(self.props.value.as_ref())
+    }
+}
+
+impl Render for RemoteSelect {
+    fn render(&self) -> Markup {
+        let has_error = self.error.is_some();
+        let theme = self.state.theme.as_ref();
+        let validation = self.state.validation_class(has_error);
+
+        let layout = self.state.field_layout(self.layout);
+        let ids = self
+            .props
+            .ids(&self.name, self.state.show_valid_feedback(has_error));
+
+        let display = self.state.field_display(self.display);
+        if display != DisplayMode::Edit {
+            return display_field(
+                &self.state,
+                display,
+                layout,
+                &self.class,
+                &self.label,
+                &ids.id,
+                self.selected_label.as_deref().unwrap_or_default(),
+            );
+        }
+
+        let value_id = format!("{}-value", ids.id);
+        let options_id = format!("{}-options", ids.id);
+
+        let attributes = Attributes::new()
+            .set("type", "search")
+            .set_opt("class", join_class(theme.input_class(), validation))
+            .set("id", &ids.id)
+            .set_opt("value", self.selected_label.as_ref())
+            .set_opt("placeholder", self.props.placeholder.as_ref())
+            .set("autocomplete", "off")
+            .set("role", "combobox")
+            .set("aria-autocomplete", "list")
+            .set("aria-expanded", "false")
+            .set("aria-controls", &options_id)
+            .flag("required", self.props.required)
+            .set_opt("aria-invalid", has_error.then_some("true"))
+            .set_opt("aria-describedby", ids.described_by())
+            .set("hx-get", &self.endpoint)
+            .set("hx-trigger", "input changed delay:300ms, focus")
+            .set("hx-target", format!("#{options_id}"))
+            .set("hx-vals", format!("js:{{{QUERY_PARAM}: this.value}}"))
+            .set("data-value-input", &value_id)
+            .set("hx-on:input", ON_INPUT)
+            .set("hx-on:keydown", ON_SEARCH_KEYDOWN)
+            .set("hx-on::after-request", ON_AFTER_REQUEST)
+            .extend(self.props.attributes());
+        let control = void_tag("input", &attributes);
+
+        let after = html!(
+            input type="hidden" name=(self.name) id=(value_id) value=[self.value()];
+            div id=(options_id) class=[join_class(theme.listbox_class(), "")] role="listbox" data-input=(ids.id) data-value-input=(value_id) hx-on:keydown=(ON_LIST_KEYDOWN) hx-on:click=(ON_PICK) {}
+            @if let (Some(feedback), Some(id)) = (&self.props.valid_feedback, &ids.feedback) {
+                div id=(id) class=[join_class(theme.valid_feedback_class(), "")] {(feedback)}
+            }
+            @if let (Some(hint), Some(id)) = (&self.props.hint, &ids.hint) {
+                div id=(id) class=[join_class(theme.hint_class(), "")] {(hint)}
+            }
+        );
+
+        theme.render_field(
+            layout,
+            FieldParts {
+                class: &self.class,
+                wrapper_id: None,
+                label: &self.label,
+                for_id: &ids.id,
+                control,
+                after,
+            },
+        )
+    }
+}
+
+///Recherche envoyée par un RemoteSelect
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionQuery {
+    pub q: String,
+    pub page: usize,
+}
+
+impl Default for OptionQuery {
+    fn default() -> Self {
+        Self {
+            q: String::new(),
+            page: 1,
+        }
+    }
+}
+
+impl OptionQuery {
+    ///A partir de la query string, une page absente ou invalide vaut 1
+    pub fn from_query(query: &str) -> Self {
+        let query = QueryString::parse(query);
+
+        Self {
+            q: query.get(QUERY_PARAM).unwrap_or_default().trim().to_owned(),
+            page: query
+                .get(PAGE_PARAM)
+                .and_then(|x| x.parse().ok())
+                .filter(|x| *x > 0)
+                .unwrap_or(1),
+        }
+    }
+}
+
+///Fragment de propositions renvoyé par l'endpoint d'un RemoteSelect
+#[derive(Clone, Debug)]
+pub struct RemoteOptions {
+    items: Vec<KeyValue>,
+    query: OptionQuery,
+    per_page: usize,
+    endpoint: String,
+    empty_label: String,
+    more_label: String,
+    state: FormState,
+}
+
+impl RemoteOptions {
+    ///Éléments dont le libellé ou la clé contient la recherche (sans tenir compte de la casse)
+    pub fn filter<A: KeyValueInterface>(items: &[A], query: &OptionQuery) -> Self {
+        let needle = query.q.to_lowercase();
+        let items = items
+            .iter()
+            .map(|x| x.to_kv())
+            .filter(|x| {
+                x.value.to_lowercase().contains(&needle) || x.key.to_lowercase().contains(&needle)
+            })
+            .collect();
+
+        Self {
+            items,
+            query: query.clone(),
+            per_page: PER_PAGE,
+            endpoint: String::new(),
+            empty_label: "Aucun résultat".to_owned(),
+            more_label: "Plus de résultats".to_owned(),
+            state: FormState::default(),
+        }
+    }
+
+    pub fn per_page(self, per_page: usize) -> Self {
+        Self {
+            per_page: per_page.max(1),
+            ..self
+        }
+    }
+
+    ///Url de la page suivante (en général celle du RemoteSelect)
+    pub fn endpoint(self, endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_owned(),
+            ..self
+        }
+    }
+
+    ///Texte affiché quand rien ne correspond à la recherche ("Aucun résultat")
+    pub fn empty_label(self, label: &str) -> Self {
+        Self {
+            empty_label: label.to_owned(),
+            ..self
+        }
+    }
+
+    ///Libellé du bouton chargeant la page suivante ("Plus de résultats")
+    pub fn more_label(self, label: &str) -> Self {
+        Self {
+            more_label: label.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Éléments de la page demandée
+    pub fn page_items(&self) -> &[KeyValue] {
+        let start = self
+            .query
+            .page
+            .saturating_sub(1)
+            .saturating_mul(self.per_page);
+        let start = start.min(self.items.len());
+        let end = (start + self.per_page).min(self.items.len());
+
+        &self.items[start..end]
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.query.page.saturating_mul(self.per_page) < self.items.len()
+    }
+
+    fn next_url(&self) -> String {
+        let query = serde_urlencoded::to_string([
+            (QUERY_PARAM, self.query.q.clone()),
+            (PAGE_PARAM, (self.query.page + 1).to_string()),
+        ])
+        .unwrap_or_default();
+        let separator = match self.endpoint.contains('?') {
+            true => '&',
+            false => '?',
+        };
+
+        format!("{}{separator}{query}", self.endpoint)
+    }
+}
+
+impl Render for RemoteOptions {
+    fn render(&self) -> Markup {
+        let class = join_class(self.state.theme.listbox_option_class(), "");
+
+        html!(
+            @for item in self.page_items() {
+                button type="button" class=[&class] role="option" data-value=(item.key) data-label=(item.value) {(item.value)}
+            }
+            @if self.items.is_empty() {
+                div class=[&class] {(self.empty_label)}
+            }
+            @if self.has_more() {
+                button type="button" class=[&class] hx-get=(self.next_url()) hx-trigger="click" hx-target="this" hx-swap="outerHTML" {(self.more_label)}
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_select() {
+        let select = RemoteSelect::new("client", "Client", "/clients/options")
+            .selected(&KeyValue::new(6, "Dupont"))
+            .layout(Layout::Stacked);
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="client" class="form-label">Client</label>"#,
+                r##"<input type="search" class="form-control" id="client" value="Dupont" autocomplete="off" role="combobox" aria-autocomplete="list" aria-expanded="false" aria-controls="client-options" hx-get="/clients/options" hx-trigger="input changed delay:300ms, focus" hx-target="#client-options" hx-vals="js:{q: this.value}" data-value-input="client-value" "##,
+                r#"hx-on:input="document.getElementById(this.dataset.valueInput).value=''" "#,
+                r#"hx-on:keydown="let l=document.getElementById(this.getAttribute('aria-controls'));if(event.key==='ArrowDown'){l.querySelector('[role=option]')?.focus();event.preventDefault()}else if(event.key==='Escape'){l.replaceChildren();this.setAttribute('aria-expanded','false')}" "#,
+                r#"hx-on::after-request="this.setAttribute('aria-expanded',event.detail.successful)">"#,
+                r#"<input type="hidden" name="client" id="client-value" value="6">"#,
+                r#"<div id="client-options" class="list-group" role="listbox" data-input="client" data-value-input="client-value" "#,
+                r#"hx-on:keydown="let o=[...this.querySelectorAll('[role=option]')],i=o.indexOf(document.activeElement),c=document.getElementById(this.dataset.input);if(event.key==='ArrowDown'&amp;&amp;i&lt;o.length-1){o[i+1].focus();event.preventDefault()}else if(event.key==='ArrowUp'){(i&gt;0?o[i-1]:c).focus();event.preventDefault()}else if(event.key==='Escape'){c.setAttribute('aria-expanded','false');c.focus();this.replaceChildren()}" "#,
+                r#"hx-on:click="let o=event.target.closest('[data-value]'),c=document.getElementById(this.dataset.input);if(o){document.getElementById(this.dataset.valueInput).value=o.dataset.value;c.value=o.dataset.label;c.setAttribute('aria-expanded','false');c.focus();this.replaceChildren()}">"#,
+                r#"</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn query() {
+        assert_eq!(
+            OptionQuery::from_query("q=%20dup%20&page=3"),
+            OptionQuery {
+                q: "dup".to_owned(),
+                page: 3
+            }
+        );
+        assert_eq!(OptionQuery::from_query("page=0"), OptionQuery::default());
+    }
+
+    #[test]
+    fn filter_and_paginate() {
+        let clients = [
+            KeyValue::new(1, "Client 1"),
+            KeyValue::new(2, "Client 2"),
+            KeyValue::new(3, "Client 3"),
+            KeyValue::new(4, "Client 4"),
+            KeyValue::new(5, "Client 5"),
+            KeyValue::new(6, "Dupont"),
+        ];
+        let options = RemoteOptions::filter(&clients, &OptionQuery::from_query("q=client"))
+            .per_page(2)
+            .endpoint("/clients/options");
+
+        assert!(options.has_more());
+        assert_eq!(
+            options.render().into_string(),
+            concat!(
+                r#"<button type="button" class="list-group-item list-group-item-action" role="option" data-value="1" data-label="Client 1">Client 1</button>"#,
+                r#"<button type="button" class="list-group-item list-group-item-action" role="option" data-value="2" data-label="Client 2">Client 2</button>"#,
+                r#"<button type="button" class="list-group-item list-group-item-action" hx-get="/clients/options?q=client&amp;page=2" hx-trigger="click" hx-target="this" hx-swap="outerHTML">Plus de résultats</button>"#,
+            )
+        );
+
+        assert!(
+            RemoteOptions::filter(&clients, &OptionQuery::from_query("q=client"))
+                .per_page(2)
+                .more_label("Suite")
+                .render()
+                .into_string()
+                .ends_with(r#"hx-swap="outerHTML">Suite</button>"#)
+        );
+
+        let last = RemoteOptions::filter(&clients, &OptionQuery::from_query("q=client&page=3"))
+            .per_page(2);
+        assert_eq!(last.page_items().len(), 1);
+        assert!(!last.has_more());
+
+        let beyond = RemoteOptions::filter(&clients, &OptionQuery::from_query("page=9"));
+        assert!(beyond.page_items().is_empty());
+    }
+
+    #[test]
+    fn no_result() {
+        let clients = [KeyValue::new(6, "Dupont")];
+        let options = RemoteOptions::filter(&clients, &OptionQuery::from_query("q=zzz"));
+
+        assert_eq!(
+            options.render().into_string(),
+            r#"<div class="list-group-item list-group-item-action">Aucun résultat</div>"#
+        );
+        assert_eq!(
+            options.empty_label("Aucun client").render().into_string(),
+            r#"<div class="list-group-item list-group-item-action">Aucun client</div>"#
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use maud::{html, Markup, Render};
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::{
+    button::{Button, ButtonSize, ButtonVariant},
+    field_props::id_from_name,
+    form::FormState,
+    form_data::FormData,
+    layout::join_class,
+};
+
+///Index remplacé à l'ajout d'une ligne dans le template
+pub const INDEX_PLACEHOLDER: &str = "__index__";
+
+///Ligne d'un Repeater, passée au template pour nommer et remplir ses champs
+#[derive(Clone, Debug)]
+pub struct RepeaterRow<'a> {
+    prefix: String,
+    index: Option<usize>,
+    data: &'a FormData,
+    errors: Option<&'a ValidationErrors>,
+}
+
+impl RepeaterRow<'_> {
+    ///name complet d'un champ de la ligne : `lines[0].qty`
+    pub fn name(&self, field: &str) -> String {
+        format!("{}.{field}", self.prefix)
+    }
+
+    ///Index de la ligne, None pour le template d'ajout
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn data(&self) -> &FormData {
+        self.data
+    }
+
+    ///Erreurs de la ligne, par nom de champ court (`qty`)
+    pub fn errors(&self) -> Option<&ValidationErrors> {
+        self.errors
+    }
+
+    pub fn has_error(&self, field: &str) -> bool {
+        self.errors.is_some_and(|x| x.errors().contains_key(field))
+    }
+}
+
+///Liste de sous-formulaires avec ajout et suppression de lignes
+///
+///Les lignes sont lues dans data (`lines[0].qty`, `lines[2].qty`...), dans l'ordre des index.
+///Les erreurs `ValidationErrorsKind::List` sont associées aux lignes dans ce même ordre.
+pub struct Repeater<F>
+where
+    F: Fn(&RepeaterRow) -> Markup,
+{
+    name: String,
+    template: F,
+    data: FormData,
+    errors: Option<ValidationErrors>,
+    min_rows: usize,
+    add_label: String,
+    remove_label: String,
+    state: FormState,
+}
+
+impl<F> Repeater<F>
+where
+    F: Fn(&RepeaterRow) -> Markup,
+{
+    pub fn new(name: &str, template: F) -> Self {
+        Self {
+            name: name.to_owned(),
+            template,
+            data: FormData::default(),
+            errors: None,
+            min_rows: 0,
+            add_label: "Ajouter une ligne".to_owned(),
+            remove_label: "Supprimer".to_owned(),
+            state: FormState::default(),
+        }
+    }
+
+    pub fn bind(self, data: &FormData) -> Self {
+        Self {
+            data: data.clone(),
+            ..self
+        }
+    }
+
+    pub fn errors(self, validation: &ValidationErrors) -> Self {
+        Self {
+            errors: Some(validation.clone()),
+            ..self
+        }
+    }
+
+    ///Nombre de lignes affichées au minimum (lignes vides ajoutées)
+    pub fn min_rows(self, min_rows: usize) -> Self {
+        Self { min_rows, ..self }
+    }
+
+    pub fn labels(self, add: &str, remove: &str) -> Self {
+        Self {
+            add_label: add.to_owned(),
+            remove_label: remove.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Erreurs de la ligne à la position donnée
+    fn row_errors(&self, position: usize) -> Option<&ValidationErrors> {
+        match self.errors.as_ref()?.errors().get(self.name.as_str())? {
+            ValidationErrorsKind::List(rows) => rows.get(&position).map(AsRef::as_ref),
+            _ => None,
+        }
+    }
+}
+
+///Index des lignes présentes dans data pour name, triés
+pub fn row_indices(data: &FormData, name: &str) -> Vec<usize> {
+    let prefix = format!("{name}[");
+    let mut indices = data
+        .entries()
+        .into_iter()
+        .filter_map(|(key, _)| key.strip_prefix(&prefix))
+        .filter_map(|rest| rest.split_once(']'))
+        .filter_map(|(index, _)| index.parse::<usize>().ok())
+        .collect::<Vec<_>>();
+    indices.sort_unstable();
+    indices.dedup();
+
+    indices
+}
+
+impl<F> Render for Repeater<F>
+where
+    F: Fn(&RepeaterRow) -> Markup,
+{
+    fn render(&self) -> Markup {
+        let theme = self.state.theme.as_ref();
+        let id = id_from_name(&self.name);
+        let rows_id = format!("{id}-rows");
+        let template_id = format!("{id}-template");
+
+        let mut indices = row_indices(&self.data, &self.name);
+        let mut next = indices.last().map(|x| x + 1).unwrap_or_default();
+        while indices.len() < self.min_rows {
+            indices.push(next);
+            next += 1;
+        }
+
+        let remove = Button::plain(&self.remove_label)
+            .variant(ButtonVariant::Danger)
+            .outline()
+            .size(ButtonSize::Small)
+            .attr("onclick", "this.closest('[data-row]').remove()")
+            .state(&self.state);
+        let add = Button::plain(&self.add_label)
+            .variant(ButtonVariant::Secondary)
+            .attr(
+                "onclick",
+                format!("let c=document.getElementById('{rows_id}');let i=+c.dataset.next;c.dataset.next=i+1;c.insertAdjacentHTML('beforeend',document.getElementById('{template_id}').innerHTML.replaceAll('{INDEX_PLACEHOLDER}',i))"),
+            )
+            .state(&self.state);
+        let row_class = join_class(theme.repeater_row_class(), "");
+
+        let empty = FormData::default();
+        let template_row = RepeaterRow {
+            prefix: format!("{}[{INDEX_PLACEHOLDER}]", self.name),
+            index: None,
+            data: &empty,
+            errors: None,
+        };
+
+        html!(
+            div id=(id) {
+                div id=(rows_id) data-next=(next) {
+                    @for (position, index) in indices.iter().enumerate() {
+                        @let row = RepeaterRow {
+                            prefix: format!("{}[{index}]", self.name),
+                            index: Some(*index),
+                            data: &self.data,
+                            errors: self.row_errors(position),
+                        };
+                        div class=[&row_class] data-row=(index) {
+                            ((self.template)(&row))
+                            (remove)
+                        }
+                    }
+                }
+                template id=(template_id) {
+                    div class=[&row_class] data-row=(INDEX_PLACEHOLDER) {
+                        ((self.template)(&template_row))
+                        (remove)
+                    }
+                }
+                (add)
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use validator::Validate;
+
+    use crate::{layout::Layout, text::TextField};
+
+    use super::*;
+
+    #[derive(Serialize, Validate)]
+    struct Line {
+        #[validate(range(min = 1))]
+        qty: u32,
+    }
+
+    #[derive(Serialize, Validate)]
+    struct Invoice {
+        #[validate]
+        lines: Vec<Line>,
+    }
+
+    fn line(row: &RepeaterRow) -> Markup {
+        TextField::number(&row.name("qty"), "Qté")
+            .layout(Layout::Inline)
+            .bind(row.data())
+            .invalid(row.has_error("qty"))
+            .render()
+    }
+
+    #[test]
+    fn indices() {
+        let data = FormData::from_pairs(&[
+            ("lines[2].qty", "1"),
+            ("lines[0].qty", "3"),
+            ("lines[0].pu", "2"),
+        ]);
+
+        assert_eq!(row_indices(&data, "lines"), vec![0, 2]);
+        assert!(row_indices(&data, "line").is_empty());
+    }
+
+    #[test]
+    fn repeater() {
+        let invoice = Invoice {
+            lines: vec![Line { qty: 2 }, Line { qty: 0 }],
+        };
+        let data = FormData::from_model(&invoice).unwrap();
+        let errors = invoice.validate().unwrap_err();
+        let repeater = Repeater::new("lines", line).bind(&data).errors(&errors);
+
+        assert_eq!(
+            repeater.render().into_string(),
+            concat!(
+                r#"<div id="lines">"#,
+                r#"<div id="lines-rows" data-next="2">"#,
+                r#"<div class="d-flex gap-2 align-items-end mb-2" data-row="0">"#,
+                r#"<div class="col-auto">"#,
+                r#"<label for="lines-0-qty" class="visually-hidden">Qté</label>"#,
+                r#"<input type="number" class="form-control" name="lines[0].qty" id="lines-0-qty" value="2">"#,
+                r#"</div>"#,
+                r#"<button type="button" class="btn btn-outline-danger btn-sm" onclick="this.closest('[data-row]').remove()">Supprimer</button>"#,
+                r#"</div>"#,
+                r#"<div class="d-flex gap-2 align-items-end mb-2" data-row="1">"#,
+                r#"<div class="col-auto">"#,
+                r#"<label for="lines-1-qty" class="visually-hidden">Qté</label>"#,
+                r#"<input type="number" class="form-control is-invalid" name="lines[1].qty" id="lines-1-qty" value="0" aria-invalid="true">"#,
+                r#"</div>"#,
+                r#"<button type="button" class="btn btn-outline-danger btn-sm" onclick="this.closest('[data-row]').remove()">Supprimer</button>"#,
+                r#"</div>"#,
+                r#"</div>"#,
+                r#"<template id="lines-template">"#,
+                r#"<div class="d-flex gap-2 align-items-end mb-2" data-row="__index__">"#,
+                r#"<div class="col-auto">"#,
+                r#"<label for="lines-__index__-qty" class="visually-hidden">Qté</label>"#,
+                r#"<input type="number" class="form-control" name="lines[__index__].qty" id="lines-__index__-qty">"#,
+                r#"</div>"#,
+                r#"<button type="button" class="btn btn-outline-danger btn-sm" onclick="this.closest('[data-row]').remove()">Supprimer</button>"#,
+                r#"</div>"#,
+                r#"</template>"#,
+                r#"<button type="button" class="btn btn-secondary" onclick="let c=document.getElementById('lines-rows');let i=+c.dataset.next;c.dataset.next=i+1;c.insertAdjacentHTML('beforeend',document.getElementById('lines-template').innerHTML.replaceAll('__index__',i))">Ajouter une ligne</button>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn min_rows() {
+        let html = Repeater::new("lines", line)
+            .min_rows(1)
+            .render()
+            .into_string();
+
+        assert!(html.contains(r#"<div id="lines-rows" data-next="1">"#));
+        assert!(html.contains(r#"name="lines[0].qty""#));
+    }
+}
@@ -0,0 +1,775 @@
+use std::{cell::Cell, collections::BTreeMap, fmt};
+
+use maud::{html, Markup, Render};
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use serde_json::{Map, Number, Value};
+use serde_path_to_error::{Path, Segment as PathSegment};
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::{
+    checkbox::Checkbox,
+    field_props::Props,
+    form::FormState,
+    form_data::FormData,
+    key_value::{KeyValue, KeyValueInterface},
+    repeater::row_indices,
+    select::Select,
+    text::TextField,
+};
+
+#[derive(Clone, Debug)]
+pub enum FieldKind {
+    Text,
+    Email,
+    Number,
+    ///Absente du corps soumis, la case vaut false
+    Checkbox,
+    Select(Vec<KeyValue>),
+    ///Liste des valeurs choisies (name répété dans le corps soumis)
+    MultiSelect(Vec<KeyValue>),
+}
+
+///Description d'un champ, utilisée pour le rendu et pour la lecture du formulaire soumis
+///
+///Un name `lignes[].qte` décrit un champ des lignes d'un Repeater : à la lecture il est répété
+///pour chaque ligne présente (`lignes[0].qte`, `lignes[3].qte`...) et donne un tableau.
+#[derive(Clone, Debug)]
+pub struct FieldSchema {
+    name: &'static str,
+    label: String,
+    kind: FieldKind,
+    required: bool,
+    class: String,
+}
+
+impl FieldSchema {
+    pub fn new(kind: FieldKind, name: &'static str, label: &str) -> Self {
+        Self {
+            name,
+            label: label.to_owned(),
+            kind,
+            required: false,
+            class: String::new(),
+        }
+    }
+
+    pub fn text(name: &'static str, label: &str) -> Self {
+        Self::new(FieldKind::Text, name, label)
+    }
+
+    pub fn email(name: &'static str, label: &str) -> Self {
+        Self::new(FieldKind::Email, name, label)
+    }
+
+    pub fn number(name: &'static str, label: &str) -> Self {
+        Self::new(FieldKind::Number, name, label)
+    }
+
+    pub fn checkbox(name: &'static str, label: &str) -> Self {
+        Self::new(FieldKind::Checkbox, name, label)
+    }
+
+    pub fn select<A: KeyValueInterface>(name: &'static str, label: &str, items: &[A]) -> Self {
+        let kvs = items.iter().map(|x| x.to_kv()).collect();
+
+        Self::new(FieldKind::Select(kvs), name, label)
+    }
+
+    pub fn multi_select<A: KeyValueInterface>(
+        name: &'static str,
+        label: &str,
+        items: &[A],
+    ) -> Self {
+        let kvs = items.iter().map(|x| x.to_kv()).collect();
+
+        Self::new(FieldKind::MultiSelect(kvs), name, label)
+    }
+
+    pub fn required(self) -> Self {
+        Self {
+            required: true,
+            ..self
+        }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: class.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn render(
+        &self,
+        data: &FormData,
+        errors: Option<&ValidationErrors>,
+        state: &FormState,
+    ) -> Markup {
+        let mut props = Props::default();
+        props.required = self.required;
+        let no_errors = ValidationErrors::new();
+        let errors = errors.unwrap_or(&no_errors);
+
+        match &self.kind {
+            FieldKind::Text | FieldKind::Email | FieldKind::Number => {
+                let field = match self.kind {
+                    FieldKind::Email => TextField::email(self.name, &self.label),
+                    FieldKind::Number => TextField::number(self.name, &self.label),
+                    _ => TextField::text(self.name, &self.label),
+                };
+
+                field
+                    .props(props)
+                    .class(&self.class)
+                    .bind(data)
+                    .errors(errors)
+                    .state(state)
+                    .render()
+            }
+            FieldKind::Checkbox => Checkbox::check(self.name, &self.label)
+                .props(props)
+                .class(&self.class)
+                .bind(data)
+                .errors(errors)
+                .state(state)
+                .render(),
+            FieldKind::Select(items) | FieldKind::MultiSelect(items) => {
+                let select = Select::new_kv(self.name, &self.label, items)
+                    .props(props)
+                    .class(&self.class);
+                let select = match self.kind {
+                    FieldKind::MultiSelect(_) => select.multiple(),
+                    _ => select,
+                };
+
+                select.bind(data).errors(errors).state(state).render()
+            }
+        }
+    }
+
+    ///Valeur json du champ lu sous name, avec l'erreur de saisie éventuelle
+    ///
+    ///Un champ texte vide vaut "" (une cible `Option<String>` reçoit donc `Some("")`).
+    fn parse(&self, name: &str, data: &FormData) -> (Value, Option<ValidationError>) {
+        let value = data.get(name).filter(|x| !x.is_empty());
+        let required =
+            |missing: bool| (self.required && missing).then(|| ValidationError::new("required"));
+
+        match (&self.kind, value) {
+            (FieldKind::Checkbox, value) => {
+                (Value::Bool(value.is_some()), required(value.is_none()))
+            }
+            (FieldKind::MultiSelect(_), _) => {
+                let values = data
+                    .get_all(name)
+                    .into_iter()
+                    .filter(|x| !x.is_empty())
+                    .map(|x| Value::String(x.to_owned()))
+                    .collect::<Vec<_>>();
+                let error = required(values.is_empty());
+
+                (Value::Array(values), error)
+            }
+            (FieldKind::Text | FieldKind::Email, None) => {
+                (Value::String(String::new()), required(true))
+            }
+            (_, None) => (Value::Null, required(true)),
+            (FieldKind::Number, Some(value)) => match parse_number(value) {
+                Some(number) => (Value::Number(number), None),
+                None => (Value::Null, Some(ValidationError::new("number"))),
+            },
+            (_, Some(value)) => (Value::String(value.to_owned()), None),
+        }
+    }
+}
+
+fn parse_number(value: &str) -> Option<Number> {
+    let value = value.trim();
+
+    value
+        .parse::<i64>()
+        .ok()
+        .map(Number::from)
+        .or_else(|| value.parse::<f64>().ok().and_then(Number::from_f64))
+}
+
+///Élément d'un name : `lignes[0].qte` donne Key(lignes), Index(0), Key(qte)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+fn segments(name: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+
+    for part in name.split('.') {
+        let (key, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+        let mut indices = Vec::new();
+        while let Some((index, tail)) = rest.strip_prefix('[').and_then(|x| x.split_once(']')) {
+            match index.parse() {
+                Ok(index) => indices.push(Segment::Index(index)),
+                Err(_) => break,
+            }
+            rest = tail;
+        }
+
+        match rest.is_empty() {
+            true => {
+                segments.push(Segment::Key(key));
+                segments.extend(indices);
+            }
+            //crochets sans index numérique : name gardé tel quel
+            false => segments.push(Segment::Key(part)),
+        }
+    }
+
+    segments
+}
+
+///Valeurs lues rangées d'après les names, avant conversion en json
+///
+///Les index d'un tableau sont triés puis renumérotés : des lignes supprimées ne laissent pas de trou.
+#[derive(Debug)]
+enum Node {
+    Value(Value),
+    Object(BTreeMap<String, Node>),
+    Array(BTreeMap<usize, Node>),
+}
+
+impl Node {
+    fn insert(&mut self, path: &[Segment], value: Value) {
+        let Some((first, rest)) = path.split_first() else {
+            *self = Node::Value(value);
+            return;
+        };
+
+        match (first, &mut *self) {
+            (Segment::Key(key), Node::Object(children)) => children
+                .entry((*key).to_owned())
+                .or_insert(Node::Value(Value::Null))
+                .insert(rest, value),
+            (Segment::Index(index), Node::Array(children)) => children
+                .entry(*index)
+                .or_insert(Node::Value(Value::Null))
+                .insert(rest, value),
+            (Segment::Key(_), _) => {
+                *self = Node::Object(BTreeMap::new());
+                self.insert(path, value);
+            }
+            (Segment::Index(_), _) => {
+                *self = Node::Array(BTreeMap::new());
+                self.insert(path, value);
+            }
+        }
+    }
+
+    ///Tableau vide si rien n'est encore rangé sous path (Repeater sans ligne)
+    fn ensure_array(&mut self, path: &[Segment]) {
+        match (path.split_first(), &mut *self) {
+            (None, Node::Value(Value::Null)) => *self = Node::Array(BTreeMap::new()),
+            (Some((Segment::Key(key), rest)), Node::Object(children)) => children
+                .entry((*key).to_owned())
+                .or_insert(Node::Value(Value::Null))
+                .ensure_array(rest),
+            _ => {}
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Node::Value(value) => value,
+            Node::Object(children) => Value::Object(
+                children
+                    .into_iter()
+                    .map(|(key, node)| (key, node.into_value()))
+                    .collect::<Map<_, _>>(),
+            ),
+            Node::Array(children) => {
+                Value::Array(children.into_values().map(Node::into_value).collect())
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SchemaError {
+    ///Corps application/x-www-form-urlencoded illisible
+    Body(serde_urlencoded::de::Error),
+    ///Champs manquants ou mal saisis, ou refusés par Validate
+    Invalid(ValidationErrors),
+    ///Les valeurs ne correspondent pas à la structure cible (hors erreur rattachée à un champ)
+    Deserialize(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Body(e) => write!(f, "corps du formulaire illisible : {e}"),
+            Self::Invalid(e) => write!(f, "formulaire invalide : {e}"),
+            Self::Deserialize(e) => write!(f, "formulaire incompatible : {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+///Lecture d'un corps urlencoded directement en T, sans FormSchema
+///
+///Une valeur refusée par serde (texte dans un nombre, champ manquant) devient une erreur
+///du champ concerné dans SchemaError::Invalid, pour ré-afficher le formulaire.
+pub fn parse_urlencoded<T: DeserializeOwned>(body: &str) -> Result<T, SchemaError> {
+    deserialize_fields(serde_urlencoded::Deserializer::new(form_urlencoded::parse(
+        body.as_bytes(),
+    )))
+}
+
+fn deserialize_fields<'de, T, D>(deserializer: D) -> Result<T, SchemaError>
+where
+    T: DeserializeOwned,
+    D: Deserializer<'de>,
+    D::Error: std::error::Error + Send + Sync + 'static,
+{
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        match field_error(struct_fields::<T>(), e.path(), &e.inner().to_string()) {
+            Some(errors) => SchemaError::Invalid(errors),
+            None => SchemaError::Deserialize(Box::new(e.into_inner())),
+        }
+    })
+}
+
+///Erreur de serde rapportée sur le champ de premier niveau concerné
+fn field_error(
+    fields: &'static [&'static str],
+    path: &Path,
+    message: &str,
+) -> Option<ValidationErrors> {
+    let (name, code) = match path.iter().next() {
+        Some(PathSegment::Map { key }) => (key.as_str(), "invalid"),
+        _ => (
+            message.split("missing field `").nth(1)?.split('`').next()?,
+            "required",
+        ),
+    };
+    //ValidationErrors n'accepte que des noms 'static : ceux déclarés à serde par T
+    let name = fields.iter().find(|x| **x == name)?;
+
+    let mut error = ValidationError::new(code);
+    error.message = Some(message.to_owned().into());
+    let mut errors = ValidationErrors::new();
+    errors.add(name, error);
+
+    Some(errors)
+}
+
+///Champs déclarés à serde par T, vide si T n'est pas une struct
+fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    let fields = Cell::new(&[][..]);
+    let _ = T::deserialize(FieldNames(&fields));
+
+    fields.get()
+}
+
+///Deserializer qui ne fait que relever la liste des champs d'une struct
+struct FieldNames<'a>(&'a Cell<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("struct attendue"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.set(fields);
+
+        Err(de::Error::custom("champs relevés"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+///Liste des champs d'un formulaire : même description pour l'affichage et la lecture
+#[derive(Clone, Debug, Default)]
+pub struct FormSchema {
+    fields: Vec<FieldSchema>,
+}
+
+impl FormSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(self, field: FieldSchema) -> Self {
+        let mut fields = self.fields;
+        fields.push(field);
+
+        Self { fields }
+    }
+
+    pub fn fields(&self) -> &[FieldSchema] {
+        &self.fields
+    }
+
+    pub fn render(
+        &self,
+        data: &FormData,
+        errors: Option<&ValidationErrors>,
+        state: &FormState,
+    ) -> Markup {
+        html!(
+            @for field in &self.fields {
+                (field.render(data, errors, state))
+            }
+        )
+    }
+
+    ///Lecture d'un corps urlencoded en T, sans passer par Validate
+    pub fn parse<T: DeserializeOwned>(&self, body: &str) -> Result<T, SchemaError> {
+        let data = FormData::from_urlencoded(body).map_err(SchemaError::Body)?;
+
+        self.parse_data(&data)
+    }
+
+    ///Lecture puis validation de T
+    pub fn parse_validated<T>(&self, body: &str) -> Result<T, SchemaError>
+    where
+        T: DeserializeOwned + Validate,
+    {
+        let value = self.parse::<T>(body)?;
+        value.validate().map_err(SchemaError::Invalid)?;
+
+        Ok(value)
+    }
+
+    pub fn parse_data<T: DeserializeOwned>(&self, data: &FormData) -> Result<T, SchemaError> {
+        let mut errors = ValidationErrors::new();
+        //erreurs des lignes, par position comme pour un Vec validé (Repeater les lit ainsi)
+        let mut row_errors = BTreeMap::<&'static str, BTreeMap<usize, ValidationErrors>>::new();
+        let mut root = Node::Object(BTreeMap::new());
+
+        for field in &self.fields {
+            match field.name.split_once("[]") {
+                Some((rows, rest)) => {
+                    root.ensure_array(&segments(rows));
+                    let key = match rest.trim_start_matches('.') {
+                        "" => rows,
+                        key => key,
+                    };
+
+                    for (position, index) in row_indices(data, rows).into_iter().enumerate() {
+                        let name = format!("{rows}[{index}]{rest}");
+                        let (value, error) = field.parse(&name, data);
+                        root.insert(&segments(&name), value);
+
+                        if let Some(error) = error {
+                            row_errors
+                                .entry(rows)
+                                .or_default()
+                                .entry(position)
+                                .or_default()
+                                .add(key, error);
+                        }
+                    }
+                }
+                None => {
+                    let (value, error) = field.parse(field.name, data);
+                    root.insert(&segments(field.name), value);
+
+                    if let Some(error) = error {
+                        errors.add(field.name, error);
+                    }
+                }
+            }
+        }
+
+        for (rows, list) in row_errors {
+            let list = list
+                .into_iter()
+                .map(|(position, errors)| (position, Box::new(errors)))
+                .collect();
+            errors
+                .errors_mut()
+                .insert(rows, ValidationErrorsKind::List(list));
+        }
+
+        if !errors.is_empty() {
+            return Err(SchemaError::Invalid(errors));
+        }
+
+        deserialize_fields(root.into_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Client {
+        #[validate(length(min = 2))]
+        nom: String,
+        email: String,
+        age: Option<u32>,
+        actif: bool,
+        tags: Vec<String>,
+    }
+
+    fn schema() -> FormSchema {
+        let tags = vec![KeyValue::new("a", "A"), KeyValue::new("b", "B")];
+
+        FormSchema::new()
+            .field(FieldSchema::text("nom", "Nom").required())
+            .field(FieldSchema::email("email", "Email"))
+            .field(FieldSchema::number("age", "Age"))
+            .field(FieldSchema::checkbox("actif", "Actif"))
+            .field(FieldSchema::multi_select("tags", "Tags", &tags))
+    }
+
+    #[test]
+    fn parse() {
+        let client = schema()
+            .parse::<Client>("nom=Dupont&age=42&tags=a&tags=b&email=")
+            .unwrap();
+
+        assert_eq!(client.nom, "Dupont");
+        assert_eq!(client.email, "");
+        assert_eq!(client.age, Some(42));
+        assert!(!client.actif);
+        assert_eq!(client.tags, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn parse_checkbox() {
+        let client = schema().parse::<Client>("nom=Dupont&actif=on").unwrap();
+
+        assert!(client.actif);
+        assert!(client.tags.is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let Err(SchemaError::Invalid(errors)) = schema().parse::<Client>("age=abc") else {
+            panic!("erreurs attendues");
+        };
+
+        let fields = errors.field_errors();
+        assert_eq!(fields["nom"][0].code, "required");
+        assert_eq!(fields["age"][0].code, "number");
+    }
+
+    #[test]
+    fn parse_validated() {
+        let Err(SchemaError::Invalid(errors)) = schema().parse_validated::<Client>("nom=D") else {
+            panic!("erreurs attendues");
+        };
+
+        assert!(errors.field_errors().contains_key("nom"));
+    }
+
+    #[test]
+    fn nested_name() {
+        #[derive(Deserialize)]
+        struct Adresse {
+            ville: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Client {
+            adresse: Adresse,
+        }
+
+        let client = FormSchema::new()
+            .field(FieldSchema::text("adresse.ville", "Ville"))
+            .parse::<Client>("adresse.ville=Lyon")
+            .unwrap();
+
+        assert_eq!(client.adresse.ville, "Lyon");
+    }
+
+    #[test]
+    fn urlencoded_field_errors() {
+        #[derive(Debug, Deserialize)]
+        struct Commande {
+            #[serde(rename = "qté")]
+            qte: u32,
+            note: String,
+        }
+
+        let Err(SchemaError::Invalid(errors)) = parse_urlencoded::<Commande>("qt%C3%A9=abc&note=x")
+        else {
+            panic!("erreur de champ attendue");
+        };
+        assert_eq!(errors.field_errors()["qté"][0].code, "invalid");
+
+        let Err(SchemaError::Invalid(errors)) = parse_urlencoded::<Commande>("qt%C3%A9=3") else {
+            panic!("erreur de champ attendue");
+        };
+        assert_eq!(errors.field_errors()["note"][0].code, "required");
+
+        let commande = parse_urlencoded::<Commande>("qt%C3%A9=3&note=x").unwrap();
+        assert_eq!((commande.qte, commande.note.as_str()), (3, "x"));
+        assert!(matches!(
+            parse_urlencoded::<u32>("x=1"),
+            Err(SchemaError::Deserialize(_))
+        ));
+    }
+
+    #[test]
+    fn empty_text() {
+        #[derive(Deserialize)]
+        struct Contact {
+            nom: String,
+            email: String,
+        }
+
+        let contact = FormSchema::new()
+            .field(FieldSchema::text("nom", "Nom"))
+            .field(FieldSchema::email("email", "Email"))
+            .parse::<Contact>("nom=")
+            .unwrap();
+
+        assert_eq!(contact.nom, "");
+        assert_eq!(contact.email, "");
+    }
+
+    #[test]
+    fn indexed_names() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Ligne {
+            article: String,
+            qte: u32,
+        }
+
+        #[derive(Deserialize)]
+        struct Commande {
+            client: String,
+            lignes: Vec<Ligne>,
+        }
+
+        let schema = FormSchema::new()
+            .field(FieldSchema::text("client", "Client"))
+            .field(FieldSchema::text("lignes[].article", "Article"))
+            .field(FieldSchema::number("lignes[].qte", "Quantité"));
+
+        //la ligne 1 a été supprimée dans le Repeater
+        let commande = schema
+            .parse::<Commande>(
+                "client=Dupont&lignes[0].article=vis&lignes[0].qte=10&lignes[2].article=clou&lignes[2].qte=5",
+            )
+            .unwrap();
+
+        assert_eq!(commande.client, "Dupont");
+        assert_eq!(
+            commande.lignes,
+            vec![
+                Ligne {
+                    article: "vis".to_owned(),
+                    qte: 10
+                },
+                Ligne {
+                    article: "clou".to_owned(),
+                    qte: 5
+                },
+            ]
+        );
+
+        let commande = schema.parse::<Commande>("client=Dupont").unwrap();
+        assert!(commande.lignes.is_empty());
+    }
+
+    #[test]
+    fn indexed_errors() {
+        #[derive(Debug, Deserialize)]
+        struct Ligne {
+            #[allow(dead_code)]
+            qte: u32,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Commande {
+            #[allow(dead_code)]
+            lignes: Vec<Ligne>,
+        }
+
+        let schema =
+            FormSchema::new().field(FieldSchema::number("lignes[].qte", "Quantité").required());
+
+        //la ligne 2 est la deuxième affichée par le Repeater
+        let Err(SchemaError::Invalid(errors)) =
+            schema.parse::<Commande>("lignes[0].qte=1&lignes[2].qte=x&lignes[3].qte=")
+        else {
+            panic!("erreurs de lignes attendues");
+        };
+        let Some(ValidationErrorsKind::List(rows)) = errors.errors().get("lignes") else {
+            panic!("erreurs de lignes attendues");
+        };
+
+        assert!(!errors.errors().contains_key("lignes[].qte"));
+        assert_eq!(rows.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(rows[&1].field_errors()["qte"][0].code, "number");
+        assert_eq!(rows[&2].field_errors()["qte"][0].code, "required");
+    }
+
+    #[test]
+    fn literal_index() {
+        #[derive(Deserialize)]
+        struct Notes {
+            notes: Vec<u32>,
+        }
+
+        let notes = FormSchema::new()
+            .field(FieldSchema::number("notes[1]", "Deuxième"))
+            .field(FieldSchema::number("notes[0]", "Première"))
+            .parse::<Notes>("notes[0]=12&notes[1]=15")
+            .unwrap();
+
+        assert_eq!(notes.notes, vec![12, 15]);
+        assert_eq!(
+            segments("a.b[2][0].c[x]"),
+            vec![
+                Segment::Key("a"),
+                Segment::Key("b"),
+                Segment::Index(2),
+                Segment::Index(0),
+                Segment::Key("c[x]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_round_trip() {
+        let data = FormData::from_urlencoded("nom=D&actif=on").unwrap();
+        let schema = FormSchema::new()
+            .field(FieldSchema::text("nom", "Nom").required())
+            .field(FieldSchema::checkbox("actif", "Actif"));
+        assert_eq!(
+            schema.render(&data, None, &FormState::new()).into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control" name="nom" id="nom" value="D" required>"#,
+                r#"<label for="nom">Nom</label>"#,
+                r#"</div>"#,
+                r#"<div class="form-check">"#,
+                r#"<input name="actif" class="form-check-input" type="checkbox" id="actif" checked>"#,
+                r#"<label for="actif" class="form-check-label">Actif</label>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+}
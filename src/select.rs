@@ -2,8 +2,15 @@ use maud::{html, Markup, Render};
 use validator::ValidationErrors;
 
 use crate::{
+    attributes::{tag, Attributes},
+    display::{display_field, DisplayMode},
     field_props::Props,
+    form::FormState,
+    form_data::FormData,
+    htmx::{field_wrapper_id, htmx_attributes, Htmx},
     key_value::{KeyValue, KeyValueInterface},
+    layout::{join_class, Layout},
+    theme::FieldParts,
 };
 
 #[derive(Clone, Debug, Default)]
@@ -13,7 +20,17 @@ pub struct Select {
     class: String,
     items: Vec<KeyValue>,
     props: Props,
+    ///Valeur reprise par bind, prioritaire sur props.value
+    bound: Option<String>,
+    ///Valeurs reprises par bind pour une sélection multiple, prioritaires sur selected
+    bound_all: Option<Vec<String>>,
     error: Option<String>,
+    state: FormState,
+    layout: Option<Layout>,
+    multiple: bool,
+    selected: Vec<String>,
+    htmx: Option<Htmx>,
+    display: Option<DisplayMode>,
 }
 
 impl Select {
@@ -28,7 +45,6 @@ impl Select {
             name: name.to_owned(),
             label: label.to_owned(),
             items: kvs,
-            class: "form-floating".to_owned(),
             ..Default::default()
         }
     }
@@ -38,7 +54,6 @@ impl Select {
             name: name.to_owned(),
             label: label.to_owned(),
             items: items.to_owned(),
-            class: "form-floating".to_owned(),
             ..Default::default()
         }
     }
@@ -56,7 +71,7 @@ impl Select {
 
     pub fn class(self, class: &str) -> Self {
         Self {
-            class: format!("{} {class}", self.class),
+            class: join_class(&self.class, class).unwrap_or_default(),
             ..self
         }
     }
@@ -70,34 +85,168 @@ impl Select {
             ..self
         }
     }
+
+    ///Champ en erreur sans passer par ValidationErrors (lignes d'un Repeater)
+    pub fn invalid(self, invalid: bool) -> Self {
+        Self {
+            error: invalid.then(String::new),
+            ..self
+        }
+    }
+
+    ///Valeur reprise de data d'après le name, prioritaire sur props.value (ou selected pour
+    ///une sélection multiple) quel que soit l'ordre des appels, multiple compris
+    pub fn bind(self, data: &FormData) -> Self {
+        Self {
+            bound: data.get(&self.name).map(str::to_owned).or(self.bound),
+            bound_all: Some(
+                data.get_all(&self.name)
+                    .into_iter()
+                    .map(str::to_owned)
+                    .collect(),
+            ),
+            ..self
+        }
+    }
+
+    ///Sélection multiple : les options choisies sont données par selected (ou bind)
+    pub fn multiple(self) -> Self {
+        Self {
+            multiple: true,
+            ..self
+        }
+    }
+
+    pub fn selected<S: ToString>(self, values: &[S]) -> Self {
+        Self {
+            selected: values.iter().map(ToString::to_string).collect(),
+            ..self
+        }
+    }
+
+    fn is_selected(&self, key: &str) -> bool {
+        match self.multiple {
+            true => self
+                .bound_all
+                .as_ref()
+                .unwrap_or(&self.selected)
+                .iter()
+                .any(|x| x == key),
+            false => self.checked_option(self.bound.clone().or(self.props.value.clone()), key),
+        }
+    }
+
+    ///Attributs htmx posés sur le select
+    pub fn htmx(self, htmx: Htmx) -> Self {
+        Self {
+            htmx: Some(htmx),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Disposition propre au champ, prioritaire sur celle du formulaire
+    pub fn layout(self, layout: Layout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    ///Mode d'affichage propre au champ, prioritaire sur celui du formulaire
+    pub fn display(self, display: DisplayMode) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
 }
 
 impl Render for Select {
     fn render(&self) -> Markup {
-        let class = self
-            .error
-            .clone()
-            .map(|_| " is-invalid".to_string())
-            .unwrap_or_default();
-
-        html!(
-            div class=(self.class) {
-            select
-                name=(self.name)
-                class={"form-select"(class)}
-                id=[self.props.clone().id]
-               {
+        let has_error = self.error.is_some();
+        let theme = self.state.theme.as_ref();
+        let validation = self.state.validation_class(has_error);
+
+        let layout = self.state.field_layout(self.layout);
+        let ids = self
+            .props
+            .ids(&self.name, self.state.show_valid_feedback(has_error));
+
+        let display = self.state.field_display(self.display);
+        if display != DisplayMode::Edit {
+            let text = self
+                .items
+                .iter()
+                .filter(|x| self.is_selected(&x.key))
+                .map(|x| x.value.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return display_field(
+                &self.state,
+                display,
+                layout,
+                &self.class,
+                &self.label,
+                &ids.id,
+                &text,
+            );
+        }
+
+        let wrapper_id = field_wrapper_id(&self.htmx, &ids.id);
+
+        let attributes = Attributes::new()
+            .set("name", &self.name)
+            .set_opt("class", join_class(theme.select_class(), validation))
+            .set("id", &ids.id)
+            .flag("multiple", self.multiple)
+            .flag("required", self.props.required)
+            .set_opt("aria-invalid", has_error.then_some("true"))
+            .set_opt("aria-describedby", ids.described_by())
+            .extend(&htmx_attributes(&self.htmx, &wrapper_id))
+            .extend(self.props.attributes());
+        let options = html!(
+            @if let Some(placeholder) = &self.props.placeholder {
+                option value="" {(placeholder)}
+            }
             @for item in &self.items {
                 option
                     value=(item.key)
-                    selected[(self.checked_option(self.props.value.clone(), &item.key))]  {(item.value)};
-            }}
-            label {(self.label)}
+                    selected[self.is_selected(&item.key)]  {(item.value)};
+            }
+        );
+        let control = tag("select", &attributes, options);
+
+        let after = html!(
+            @if let (Some(feedback), Some(id)) = (&self.props.valid_feedback, &ids.feedback) {
+                div id=(id) class=[join_class(theme.valid_feedback_class(), "")] {(feedback)}
+            }
+            @if let (Some(hint), Some(id)) = (&self.props.hint, &ids.hint) {
+                div id=(id) class=[join_class(theme.hint_class(), "")] {(hint)}
+            }
             //voir avec crate Validator
             // @if let Some(error) = self.error.get(&self.name) {
             //     div class="invalid-feedback" {(error)}
             // }
-        })
+        );
+
+        theme.render_field(
+            layout,
+            FieldParts {
+                class: &self.class,
+                wrapper_id,
+                label: &self.label,
+                for_id: &ids.id,
+                control,
+                after,
+            },
+        )
     }
 }
 
@@ -107,8 +256,12 @@ mod tests {
     use validator::Validate;
 
     use crate::{
+        display::DisplayMode,
         field_props::Props,
+        form::FormState,
+        form_data::FormData,
         key_value::{KeyValue, KeyValueInterface},
+        layout::Layout,
         select::Select,
     };
 
@@ -158,11 +311,11 @@ mod tests {
             with_selected_option.into_string(),
             concat!(
                 r#"<div class="form-floating mb-4">"#,
-                r#"<select name="mon_select" class="form-select">"#,
+                r#"<select name="mon_select" class="form-select" id="mon_select">"#,
                 r#"<option value="1">A</option>"#,
                 r#"<option value="2">B</option>"#,
                 r#"</select>"#,
-                r#"<label>choisir</label>"#,
+                r#"<label for="mon_select">choisir</label>"#,
                 r#"</div>"#,
             )
         );
@@ -180,11 +333,11 @@ mod tests {
             with_selected_option.into_string(),
             concat!(
                 r#"<div class="form-floating">"#,
-                r#"<select name="mon_select" class="form-select">"#,
+                r#"<select name="mon_select" class="form-select" id="mon_select">"#,
                 r#"<option value="1">A</option>"#,
                 r#"<option value="2" selected>B</option>"#,
                 r#"</select>"#,
-                r#"<label>choisir</label>"#,
+                r#"<label for="mon_select">choisir</label>"#,
                 r#"</div>"#,
             )
         );
@@ -208,7 +361,7 @@ mod tests {
                 r#"<option value="1">A</option>"#,
                 r#"<option value="2" selected>B</option>"#,
                 r#"</select>"#,
-                r#"<label>choisir</label>"#,
+                r#"<label for="my_id">choisir</label>"#,
                 r#"</div>"#,
             )
         );
@@ -229,12 +382,197 @@ mod tests {
             select.render().into_string(),
             concat!(
                 r#"<div class="form-floating">"#,
-                r#"<select name="id" class="form-select is-invalid">"#,
+                r#"<select name="id" class="form-select is-invalid" id="id" aria-invalid="true">"#,
                 r#"<option value="1"></option>"#,
                 r#"</select>"#,
-                r#"<label></label>"#,
+                r#"<label for="id"></label>"#,
+                r#"</div>"#,
+            )
+        )
+    }
+
+    #[test]
+    fn select_valid_after_submit() {
+        let toto = Toto {
+            id: 0,
+            code: "".to_owned(),
+        };
+
+        let validation = toto.validate().unwrap_err();
+        let state = FormState::new().submitted(true);
+
+        let select = Select::simple("code", "", &[Toto::new(1, "")])
+            .props(Props::default().valid_feedback("Ok"))
+            .errors(&validation)
+            .state(&state);
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<select name="code" class="form-select is-valid" id="code" aria-describedby="code-feedback">"#,
+                r#"<option value="1"></option>"#,
+                r#"</select>"#,
+                r#"<label for="code"></label>"#,
+                r#"<div id="code-feedback" class="valid-feedback">Ok</div>"#,
+                r#"</div>"#,
+            )
+        )
+    }
+
+    #[test]
+    fn select_stacked() {
+        let items = vec![KeyValue::new("1", "A")];
+        let select = Select::new_kv("mon_select", "choisir", &items)
+            .layout(Layout::Stacked)
+            .class("mb-3");
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div class="mb-3">"#,
+                r#"<label for="mon_select" class="form-label">choisir</label>"#,
+                r#"<select name="mon_select" class="form-select" id="mon_select">"#,
+                r#"<option value="1">A</option>"#,
+                r#"</select>"#,
+                r#"</div>"#,
+            )
+        )
+    }
+
+    #[test]
+    fn select_horizontal() {
+        let items = vec![KeyValue::new("1", "A")];
+        let state = FormState::new().layout(Layout::horizontal(4, 8));
+        let select = Select::new_kv("mon_select", "choisir", &items).state(&state);
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div class="row">"#,
+                r#"<label for="mon_select" class="col-sm-4 col-form-label">choisir</label>"#,
+                r#"<div class="col-sm-8">"#,
+                r#"<select name="mon_select" class="form-select" id="mon_select">"#,
+                r#"<option value="1">A</option>"#,
+                r#"</select>"#,
+                r#"</div>"#,
                 r#"</div>"#,
             )
         )
     }
+
+    #[test]
+    fn select_bind() {
+        #[derive(serde::Serialize)]
+        struct Model {
+            mon_select: i32,
+        }
+
+        let data = FormData::from_model(&Model { mon_select: 2 }).unwrap();
+        let items = vec![KeyValue::new("1", "A"), KeyValue::new("2", "B")];
+
+        let select = Select::new_kv("mon_select", "choisir", &items).bind(&data);
+
+        assert!(select
+            .render()
+            .into_string()
+            .contains(r#"<option value="2" selected>B</option>"#));
+    }
+
+    #[test]
+    fn select_multiple() {
+        let items = vec![
+            KeyValue::new("a", "A"),
+            KeyValue::new("b", "B"),
+            KeyValue::new("c", "C"),
+        ];
+        let data = FormData::from_pairs(&[("tags", "a"), ("tags", "c")]);
+
+        let select = Select::new_kv("tags", "Tags", &items)
+            .multiple()
+            .bind(&data);
+        //bind avant multiple : les valeurs sont choisies au rendu
+        let bound_first = Select::new_kv("tags", "Tags", &items)
+            .bind(&data)
+            .multiple();
+
+        assert_eq!(
+            bound_first.render().into_string(),
+            select.render().into_string()
+        );
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<select name="tags" class="form-select" id="tags" multiple>"#,
+                r#"<option value="a" selected>A</option>"#,
+                r#"<option value="b">B</option>"#,
+                r#"<option value="c" selected>C</option>"#,
+                r#"</select>"#,
+                r#"<label for="tags">Tags</label>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn select_attributes() {
+        let items = vec![KeyValue::new("a", "A")];
+        let select =
+            Select::new_kv("code", "Code", &items).props(Props::default().disabled().tabindex(2));
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<select name="code" class="form-select" id="code" disabled tabindex="2">"#,
+                r#"<option value="a">A</option>"#,
+                r#"</select>"#,
+                r#"<label for="code">Code</label>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn select_hint() {
+        let items = vec![KeyValue::new("a", "A")];
+        let select = Select::new_kv("code", "Code", &items)
+            .props(Props::default().hint("aide"))
+            .layout(Layout::Stacked);
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="code" class="form-label">Code</label>"#,
+                r#"<select name="code" class="form-select" id="code" aria-describedby="code-hint">"#,
+                r#"<option value="a">A</option>"#,
+                r#"</select>"#,
+                r#"<div id="code-hint" class="form-text">aide</div>"#,
+                r#"</div>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn select_display() {
+        let items = vec![
+            KeyValue::new("fr", "France"),
+            KeyValue::new("be", "Belgique"),
+        ];
+        let select = Select::new_kv("pays", "Pays", &items)
+            .props(Props::new_value(Some("be")))
+            .display(DisplayMode::Plaintext);
+
+        assert_eq!(
+            select.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control-plaintext" id="pays" value="Belgique" readonly>"#,
+                r#"<label for="pays">Pays</label>"#,
+                r#"</div>"#,
+            )
+        );
+    }
 }
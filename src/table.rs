@@ -0,0 +1,419 @@
+use maud::{html, Markup, Render};
+
+use crate::{
+    form::FormState,
+    layout::join_class,
+    pagination::Pagination,
+    query::{QueryString, PAGE_PARAM},
+};
+
+///Paramètre portant la clé de tri
+pub const SORT_PARAM: &str = "sort";
+///Paramètre portant le sens du tri (`asc` ou `desc`)
+pub const ORDER_PARAM: &str = "order";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+///Tri demandé dans la query string, appliqué par l'appelant (requête sql...)
+///
+///Seules les clés autorisées sont acceptées : key peut servir à choisir une colonne sql.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sort {
+    pub key: String,
+    pub order: SortOrder,
+}
+
+impl Sort {
+    ///None sans clé de tri ou avec une clé absente de allowed, un sens invalide vaut Asc
+    pub fn from_query(query: &str, allowed: &[&str]) -> Option<Self> {
+        Self::from_query_string(&QueryString::parse(query), allowed)
+    }
+
+    pub(crate) fn from_query_string(query: &QueryString, allowed: &[&str]) -> Option<Self> {
+        let key = query.get(SORT_PARAM).filter(|x| allowed.contains(x))?;
+
+        Some(Self {
+            key: key.to_owned(),
+            order: match query.get(ORDER_PARAM) {
+                Some("desc") => SortOrder::Desc,
+                _ => SortOrder::Asc,
+            },
+        })
+    }
+}
+
+///Colonne d'un Table : en-tête, contenu de la cellule et clé de tri éventuelle
+pub struct Column<'a, T> {
+    header: String,
+    cell: Box<dyn Fn(&T) -> Markup + 'a>,
+    sort: Option<String>,
+    class: String,
+}
+
+impl<'a, T> Column<'a, T> {
+    pub fn new<R, F>(header: &str, cell: F) -> Self
+    where
+        R: Render,
+        F: Fn(&T) -> R + 'a,
+    {
+        Self {
+            header: header.to_owned(),
+            cell: Box::new(move |x| cell(x).render()),
+            sort: None,
+            class: String::new(),
+        }
+    }
+
+    ///Colonne triable, key est envoyée dans le paramètre `sort`
+    pub fn sort(self, key: &str) -> Self {
+        Self {
+            sort: Some(key.to_owned()),
+            ..self
+        }
+    }
+
+    ///Classe de l'en-tête et des cellules (alignement...)
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+}
+
+///Tableau d'une page de liste, le tri et la pagination passent par des liens
+///
+///Les liens conservent la query string courante (filtres), changer le tri revient à la page 1.
+pub struct Table<'a, T> {
+    items: &'a [T],
+    columns: Vec<Column<'a, T>>,
+    query: QueryString,
+    caption: Option<String>,
+    empty: String,
+    class: String,
+    responsive: bool,
+    pagination: Option<Pagination>,
+    state: FormState,
+}
+
+impl<'a, T> Table<'a, T> {
+    pub fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            columns: Vec::new(),
+            query: QueryString::default(),
+            caption: None,
+            empty: "Aucun élément".to_owned(),
+            class: String::new(),
+            responsive: true,
+            pagination: None,
+            state: FormState::default(),
+        }
+    }
+
+    pub fn column(self, column: Column<'a, T>) -> Self {
+        let mut columns = self.columns;
+        columns.push(column);
+
+        Self { columns, ..self }
+    }
+
+    ///Query string courante, reprise dans les liens de tri et de pagination
+    pub fn query(self, query: &str) -> Self {
+        Self {
+            query: QueryString::parse(query),
+            ..self
+        }
+    }
+
+    pub fn caption(self, caption: &str) -> Self {
+        Self {
+            caption: Some(caption.to_owned()),
+            ..self
+        }
+    }
+
+    ///Texte affiché quand items est vide
+    pub fn empty(self, text: &str) -> Self {
+        Self {
+            empty: text.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        Self {
+            class: join_class(&self.class, class).unwrap_or_default(),
+            ..self
+        }
+    }
+
+    ///Conteneur défilant horizontalement sur petit écran (actif par défaut)
+    pub fn responsive(self, responsive: bool) -> Self {
+        Self { responsive, ..self }
+    }
+
+    ///Pagination affichée sous le tableau, avec la query du tableau
+    pub fn pagination(self, pagination: Pagination) -> Self {
+        Self {
+            pagination: Some(pagination),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Tri de la query, limité aux clés déclarées par Column::sort
+    pub fn sort(&self) -> Option<Sort> {
+        let keys = self
+            .columns
+            .iter()
+            .filter_map(|x| x.sort.as_deref())
+            .collect::<Vec<_>>();
+
+        Sort::from_query_string(&self.query, &keys)
+    }
+
+    fn header(&self, column: &Column<T>) -> Markup {
+        let class = join_class(&column.class, "");
+        let Some(key) = &column.sort else {
+            return html!(th scope="col" class=[class] {(column.header)});
+        };
+
+        let current = Sort::from_query_string(&self.query, &[key]);
+        let next = match &current {
+            Some(Sort {
+                order: SortOrder::Asc,
+                ..
+            }) => SortOrder::Desc,
+            _ => SortOrder::Asc,
+        };
+        let href = self
+            .query
+            .clone()
+            .remove(PAGE_PARAM)
+            .set(SORT_PARAM, key)
+            .set(ORDER_PARAM, next.as_str())
+            .href();
+        let (aria_sort, arrow) = match current.map(|x| x.order) {
+            Some(SortOrder::Asc) => (Some("ascending"), Some("▲")),
+            Some(SortOrder::Desc) => (Some("descending"), Some("▼")),
+            None => (None, None),
+        };
+
+        html!(
+            th scope="col" class=[class] aria-sort=[aria_sort] {
+                a class=[join_class(self.state.theme.sort_link_class(), "")] href=(href) {
+                    (column.header)
+                    @if let Some(arrow) = arrow {
+                        " "
+                        span aria-hidden="true" {(arrow)}
+                    }
+                }
+            }
+        )
+    }
+}
+
+impl<T> Render for Table<'_, T> {
+    fn render(&self) -> Markup {
+        let theme = self.state.theme.as_ref();
+
+        let table = html!(
+            table class=[join_class(theme.table_class(), &self.class)] {
+                @if let Some(caption) = &self.caption {
+                    caption {(caption)}
+                }
+                thead {
+                    tr {
+                        @for column in &self.columns {
+                            (self.header(column))
+                        }
+                    }
+                }
+                tbody {
+                    @for item in self.items {
+                        tr {
+                            @for column in &self.columns {
+                                td class=[join_class(&column.class, "")] {((column.cell)(item))}
+                            }
+                        }
+                    }
+                    @if self.items.is_empty() {
+                        tr {
+                            td colspan=(self.columns.len().max(1)) class=[join_class(theme.table_empty_class(), "")] {(self.empty)}
+                        }
+                    }
+                }
+            }
+        );
+        let pagination = self.pagination.clone().map(|x| {
+            x.query_string(self.query.clone())
+                .state(&self.state)
+                .render()
+        });
+
+        html!(
+            @if self.responsive {
+                div class=[join_class(theme.table_responsive_class(), "")] {(table)}
+            } @else {
+                (table)
+            }
+            (pagination.unwrap_or_default())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Client {
+        name: String,
+        orders: u32,
+    }
+
+    #[test]
+    fn sort() {
+        let allowed = ["name", "orders"];
+
+        assert_eq!(
+            Sort::from_query("sort=name&order=desc", &allowed),
+            Some(Sort {
+                key: "name".to_owned(),
+                order: SortOrder::Desc
+            })
+        );
+        assert_eq!(
+            Sort::from_query("sort=name&order=x", &allowed)
+                .unwrap()
+                .order,
+            SortOrder::Asc
+        );
+        assert_eq!(Sort::from_query("order=desc", &allowed), None);
+        assert_eq!(Sort::from_query("sort=", &allowed), None);
+        assert_eq!(
+            Sort::from_query("sort=name;drop%20table%20clients", &allowed),
+            None
+        );
+    }
+
+    #[test]
+    fn table_sort() {
+        let items = [Client {
+            name: "Dupont".to_owned(),
+            orders: 3,
+        }];
+        let sort = |query: &str| {
+            Table::new(&items)
+                .column(Column::new("Nom", |x: &Client| x.name.clone()).sort("name"))
+                .column(Column::new("Commandes", |x: &Client| x.orders))
+                .query(query)
+                .sort()
+        };
+
+        assert_eq!(
+            sort("sort=name&order=desc"),
+            Some(Sort {
+                key: "name".to_owned(),
+                order: SortOrder::Desc
+            })
+        );
+        //Commandes n'est pas triable
+        assert_eq!(sort("sort=orders"), None);
+        assert_eq!(sort("sort=password"), None);
+    }
+
+    #[test]
+    fn table_sorted() {
+        let items = [
+            Client {
+                name: "Dupont".to_owned(),
+                orders: 3,
+            },
+            Client {
+                name: "Martin".to_owned(),
+                orders: 12,
+            },
+        ];
+        let table = Table::new(&items)
+            .column(Column::new("Nom", |x: &Client| x.name.clone()).sort("name"))
+            .column(Column::new("Commandes", |x: &Client| x.orders).class("text-end"))
+            .query("q=du&sort=name&order=asc&page=2")
+            .pagination(Pagination::new(30, 10, 2));
+
+        assert_eq!(
+            table.render().into_string(),
+            concat!(
+                r#"<div class="table-responsive">"#,
+                r#"<table class="table">"#,
+                r#"<thead><tr>"#,
+                r#"<th scope="col" aria-sort="ascending">"#,
+                r#"<a class="link-body-emphasis text-decoration-none" href="?q=du&amp;sort=name&amp;order=desc">Nom <span aria-hidden="true">▲</span></a>"#,
+                r#"</th>"#,
+                r#"<th scope="col" class="text-end">Commandes</th>"#,
+                r#"</tr></thead>"#,
+                r#"<tbody>"#,
+                r#"<tr><td>Dupont</td><td class="text-end">3</td></tr>"#,
+                r#"<tr><td>Martin</td><td class="text-end">12</td></tr>"#,
+                r#"</tbody>"#,
+                r#"</table>"#,
+                r#"</div>"#,
+                r#"<nav aria-label="Pagination">"#,
+                r#"<ul class="pagination">"#,
+                r#"<li class="page-item"><a class="page-link" href="?q=du&amp;sort=name&amp;order=asc">Précédent</a></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="?q=du&amp;sort=name&amp;order=asc">1</a></li>"#,
+                r#"<li class="page-item active"><a class="page-link" href="?q=du&amp;sort=name&amp;order=asc&amp;page=2" aria-current="page">2</a></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="?q=du&amp;sort=name&amp;order=asc&amp;page=3">3</a></li>"#,
+                r#"<li class="page-item"><a class="page-link" href="?q=du&amp;sort=name&amp;order=asc&amp;page=3">Suivant</a></li>"#,
+                r#"</ul>"#,
+                r#"</nav>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn table_empty() {
+        let table = Table::new(&[])
+            .column(Column::new("Nom", |x: &Client| x.name.clone()).sort("name"))
+            .column(Column::new("Commandes", |x: &Client| x.orders).class("text-end"))
+            .caption("Clients")
+            .empty("Aucun client")
+            .responsive(false);
+
+        assert_eq!(
+            table.render().into_string(),
+            concat!(
+                r#"<table class="table">"#,
+                r#"<caption>Clients</caption>"#,
+                r#"<thead><tr>"#,
+                r#"<th scope="col"><a class="link-body-emphasis text-decoration-none" href="?sort=name&amp;order=asc">Nom</a></th>"#,
+                r#"<th scope="col" class="text-end">Commandes</th>"#,
+                r#"</tr></thead>"#,
+                r#"<tbody>"#,
+                r#"<tr><td colspan="2" class="text-center text-body-secondary">Aucun client</td></tr>"#,
+                r#"</tbody>"#,
+                r#"</table>"#,
+            )
+        );
+    }
+}
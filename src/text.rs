@@ -1,7 +1,18 @@
 use maud::{html, Markup, Render};
 use validator::ValidationErrors;
 
-use crate::field_props::Props;
+use crate::{
+    addon::Addon,
+    attributes::{void_tag, Attributes},
+    display::{display_field, DisplayMode},
+    field_props::Props,
+    form::FormState,
+    form_data::FormData,
+    htmx::{field_wrapper_id, htmx_attributes, Htmx},
+    key_value::{KeyValue, KeyValueInterface},
+    layout::{join_class, Layout},
+    theme::FieldParts,
+};
 
 #[derive(Clone, Debug, Default)]
 pub enum TextFieldType {
@@ -10,6 +21,7 @@ pub enum TextFieldType {
 
     Email,
     Number,
+    Date,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -19,7 +31,16 @@ pub struct TextField {
     my_type: TextFieldType,
     class: String,
     props: Props,
+    ///Valeur reprise par bind, prioritaire sur props.value
+    bound: Option<String>,
     error: Option<String>,
+    state: FormState,
+    layout: Option<Layout>,
+    prefix: Option<Addon>,
+    suffix: Option<Addon>,
+    htmx: Option<Htmx>,
+    display: Option<DisplayMode>,
+    suggestions: Vec<KeyValue>,
 }
 
 impl TextField {
@@ -35,11 +56,15 @@ impl TextField {
         Self::new(TextFieldType::Number, name, label)
     }
 
+    ///Date au format `AAAA-MM-JJ`
+    pub fn date(name: &str, label: &str) -> Self {
+        Self::new(TextFieldType::Date, name, label)
+    }
+
     fn new(my_type: TextFieldType, name: &str, label: &str) -> Self {
         Self {
             name: name.to_owned(),
             label: label.to_owned(),
-            class: "form-floating".to_owned(),
             my_type,
             ..Default::default()
         }
@@ -51,7 +76,7 @@ impl TextField {
 
     pub fn class(self, class: &str) -> Self {
         Self {
-            class: format!("{} {class}", self.class),
+            class: join_class(&self.class, class).unwrap_or_default(),
             ..self
         }
     }
@@ -65,6 +90,82 @@ impl TextField {
             ..self
         }
     }
+
+    ///Champ en erreur sans passer par ValidationErrors (lignes d'un Repeater)
+    pub fn invalid(self, invalid: bool) -> Self {
+        Self {
+            error: invalid.then(String::new),
+            ..self
+        }
+    }
+
+    ///Valeur reprise de data d'après le name, prioritaire sur props.value quel que soit l'ordre des appels
+    pub fn bind(self, data: &FormData) -> Self {
+        Self {
+            bound: data.get(&self.name).map(str::to_owned).or(self.bound),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    ///Disposition propre au champ, prioritaire sur celle du formulaire
+    pub fn layout(self, layout: Layout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    ///Mode d'affichage propre au champ, prioritaire sur celui du formulaire
+    pub fn display(self, display: DisplayMode) -> Self {
+        Self {
+            display: Some(display),
+            ..self
+        }
+    }
+
+    ///Élément placé avant le champ (input-group)
+    pub fn prefix(self, addon: Addon) -> Self {
+        Self {
+            prefix: Some(addon),
+            ..self
+        }
+    }
+
+    ///Élément placé après le champ (input-group)
+    pub fn suffix(self, addon: Addon) -> Self {
+        Self {
+            suffix: Some(addon),
+            ..self
+        }
+    }
+
+    ///Suggestions de saisie (datalist), la saisie reste libre
+    pub fn suggestions<A: KeyValueInterface>(self, items: &[A]) -> Self {
+        Self {
+            suggestions: items.iter().map(|x| x.to_kv()).collect(),
+            ..self
+        }
+    }
+
+    ///Attributs htmx posés sur l'input
+    pub fn htmx(self, htmx: Htmx) -> Self {
+        Self {
+            htmx: Some(htmx),
+            ..self
+        }
+    }
+
+    ///Valeur affichée : celle reprise par bind, sinon props.value
+    fn value(&self) -> Option<&String> {
+        self.bound.as_ref().or(self.props.value.as_ref())
+    }
 }
 
 impl Render for TextField {
@@ -73,29 +174,128 @@ impl Render for TextField {
             TextFieldType::Text => "text",
             TextFieldType::Email => "email",
             TextFieldType::Number => "number",
+            TextFieldType::Date => "date",
         };
 
-        let class = self
-            .error
-            .clone()
-            .map(|_| " is-invalid".to_string())
-            .unwrap_or_default();
-
-        html!(
-            div class=(self.class) {
-                input
-                    type=(type_str)
-                    class={"form-control"(class)}
-                    name=(self.name)
-                    id=[self.props.clone().id]
-                    value=[self.props.value.clone()]
-                    placeholder=[self.props.clone().placeholder];
-                label {(self.label)}
-                @if let Some(hint) = self.props.clone().hint {
-                    div class="form-text" {(hint)}
+        let has_error = self.error.is_some();
+        let theme = self.state.theme.as_ref();
+        let validation = self.state.validation_class(has_error);
+
+        let layout = self.state.field_layout(self.layout);
+        let ids = self
+            .props
+            .ids(&self.name, self.state.show_valid_feedback(has_error));
+
+        let display = self.state.field_display(self.display);
+        if display != DisplayMode::Edit {
+            let text = self.value().map(String::as_str).unwrap_or_default();
+            return display_field(
+                &self.state,
+                display,
+                layout,
+                &self.class,
+                &self.label,
+                &ids.id,
+                text,
+            );
+        }
+
+        let wrapper_id = field_wrapper_id(&self.htmx, &ids.id);
+        let list_id = (!self.suggestions.is_empty()).then(|| format!("{}-list", ids.id));
+
+        let attributes = Attributes::new()
+            .set("type", type_str)
+            .set_opt("class", join_class(theme.input_class(), validation))
+            .set("name", &self.name)
+            .set("id", &ids.id)
+            .set_opt("value", self.value())
+            .set_opt("placeholder", self.props.placeholder.as_ref())
+            .set_opt("list", list_id.as_ref())
+            .flag("required", self.props.required)
+            .set_opt("aria-invalid", has_error.then_some("true"))
+            .set_opt("aria-describedby", ids.described_by())
+            .extend(&htmx_attributes(&self.htmx, &wrapper_id))
+            .extend(self.props.attributes());
+        let control = html!(
+            (void_tag("input", &attributes))
+            @if let Some(list_id) = &list_id {
+                datalist id=(list_id) {
+                    @for item in &self.suggestions {
+                        option value=(item.key) {(item.value)}
+                    }
                 }
             }
-        )
+        );
+
+        let feedback = html!(
+            @if let (Some(feedback), Some(id)) = (&self.props.valid_feedback, &ids.feedback) {
+                div id=(id) class=[join_class(theme.valid_feedback_class(), "")] {(feedback)}
+            }
+        );
+
+        let hint = html!(
+            @if let (Some(hint), Some(id)) = (&self.props.hint, &ids.hint) {
+                div id=(id) class=[join_class(theme.hint_class(), "")] {(hint)}
+            }
+        );
+
+        if self.prefix.is_none() && self.suffix.is_none() {
+            return theme.render_field(
+                layout,
+                FieldParts {
+                    class: &self.class,
+                    wrapper_id,
+                    label: &self.label,
+                    for_id: &ids.id,
+                    control,
+                    after: html!((feedback)(hint)),
+                },
+            );
+        }
+
+        //le feedback doit rester dans l'input-group, le hint en dehors
+        let group_class = join_class(theme.input_group_class(!validation.is_empty()), "");
+        let prefix = self.prefix.as_ref().map(|x| x.render_with(theme));
+        let suffix = self.suffix.as_ref().map(|x| x.render_with(theme));
+
+        match layout {
+            Layout::Floating => html!(
+                div id=[wrapper_id] class=[join_class("", &self.class)] {
+                    div class=[group_class] {
+                        (prefix.unwrap_or_default())
+                        div class=[join_class(theme.wrapper_class(layout), validation)] {
+                            (control)
+                            label for=(ids.id) {(self.label)}
+                        }
+                        (suffix.unwrap_or_default())
+                        (feedback)
+                    }
+                    (hint)
+                }
+            ),
+            _ => {
+                let group = html!(
+                    div class=[group_class] {
+                        (prefix.unwrap_or_default())
+                        (control)
+                        (suffix.unwrap_or_default())
+                        (feedback)
+                    }
+                );
+
+                theme.render_field(
+                    layout,
+                    FieldParts {
+                        class: &self.class,
+                        wrapper_id,
+                        label: &self.label,
+                        for_id: &ids.id,
+                        control: group,
+                        after: hint,
+                    },
+                )
+            }
+        }
     }
 }
 
@@ -104,6 +304,11 @@ mod tests {
 
     use validator::Validate;
 
+    use crate::{
+        htmx::Swap,
+        theme::{Plain, Tailwind},
+    };
+
     use super::*;
 
     #[derive(Validate)]
@@ -121,8 +326,8 @@ mod tests {
             text.render().into_string(),
             concat!(
                 r#"<div class="form-floating">"#,
-                r#"<input type="text" class="form-control" name="name">"#,
-                r#"<label>Name</label></div>"#
+                r#"<input type="text" class="form-control" name="name" id="name">"#,
+                r#"<label for="name">Name</label></div>"#
             )
         );
     }
@@ -135,8 +340,8 @@ mod tests {
             text.render().into_string(),
             concat!(
                 r#"<div class="form-floating mb-3">"#,
-                r#"<input type="text" class="form-control" name="name">"#,
-                r#"<label>Name</label></div>"#
+                r#"<input type="text" class="form-control" name="name" id="name">"#,
+                r#"<label for="name">Name</label></div>"#
             )
         );
     }
@@ -151,9 +356,9 @@ mod tests {
             text.render().into_string(),
             concat!(
                 r#"<div class="form-floating mb-3">"#,
-                r#"<input type="text" class="form-control" name="name" id="my_id">"#,
-                r#"<label>Name</label>"#,
-                r#"<div class="form-text">indice</div>"#,
+                r#"<input type="text" class="form-control" name="name" id="my_id" aria-describedby="my_id-hint">"#,
+                r#"<label for="my_id">Name</label>"#,
+                r#"<div id="my_id-hint" class="form-text">indice</div>"#,
                 r#"</div>"#
             )
         );
@@ -169,9 +374,9 @@ mod tests {
             text.render().into_string(),
             concat!(
                 r#"<div class="form-floating mb-3">"#,
-                r#"<input type="text" class="form-control" name="name">"#,
-                r#"<label>Name</label>"#,
-                r#"<div class="form-text">indice</div>"#,
+                r#"<input type="text" class="form-control" name="name" id="name" aria-describedby="name-hint">"#,
+                r#"<label for="name">Name</label>"#,
+                r#"<div id="name-hint" class="form-text">indice</div>"#,
                 r#"</div>"#
             )
         );
@@ -185,8 +390,8 @@ mod tests {
             text.render().into_string(),
             concat!(
                 r#"<div class="form-floating">"#,
-                r#"<input type="email" class="form-control" name="name">"#,
-                r#"<label>Name</label>"#,
+                r#"<input type="email" class="form-control" name="name" id="name">"#,
+                r#"<label for="name">Name</label>"#,
                 r#"</div>"#
             )
         );
@@ -208,8 +413,416 @@ mod tests {
             text.render().into_string(),
             concat!(
                 r#"<div class="form-floating">"#,
-                r#"<input type="text" class="form-control is-invalid" name="id">"#,
-                r#"<label>Name</label>"#,
+                r#"<input type="text" class="form-control is-invalid" name="id" id="id" aria-invalid="true">"#,
+                r#"<label for="id">Name</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_valid_after_submit() {
+        let toto = Toto {
+            id: 0,
+            code: "".to_owned(),
+        };
+
+        let validation = toto.validate().unwrap_err();
+        let state = FormState::new().submitted(true);
+        let text = TextField::text("code", "Code")
+            .props(Props::default().valid_feedback("Parfait"))
+            .errors(&validation)
+            .state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control is-valid" name="code" id="code" aria-describedby="code-feedback">"#,
+                r#"<label for="code">Code</label>"#,
+                r#"<div id="code-feedback" class="valid-feedback">Parfait</div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_error_after_submit() {
+        let toto = Toto {
+            id: 0,
+            code: "".to_owned(),
+        };
+
+        let validation = toto.validate().unwrap_err();
+        let state = FormState::new().submitted(true);
+        let text = TextField::text("id", "Name")
+            .props(Props::default().valid_feedback("Parfait"))
+            .errors(&validation)
+            .state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control is-invalid" name="id" id="id" aria-invalid="true">"#,
+                r#"<label for="id">Name</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_valid_feedback_not_submitted() {
+        let text =
+            TextField::text("code", "Code").props(Props::default().valid_feedback("Parfait"));
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control" name="code" id="code">"#,
+                r#"<label for="code">Code</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_stacked() {
+        let text = TextField::text("name", "Name")
+            .layout(Layout::Stacked)
+            .class("mb-3")
+            .props(Props::default().hint("indice"));
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="mb-3">"#,
+                r#"<label for="name" class="form-label">Name</label>"#,
+                r#"<input type="text" class="form-control" name="name" id="name" aria-describedby="name-hint">"#,
+                r#"<div id="name-hint" class="form-text">indice</div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_horizontal_from_state() {
+        let state = FormState::new().layout(Layout::horizontal(2, 10));
+        let text = TextField::text("name", "Name").state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="row">"#,
+                r#"<label for="name" class="col-sm-2 col-form-label">Name</label>"#,
+                r#"<div class="col-sm-10">"#,
+                r#"<input type="text" class="form-control" name="name" id="name">"#,
+                r#"</div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_field_layout_overrides_state() {
+        let state = FormState::new().layout(Layout::Stacked);
+        let text = TextField::text("name", "Name")
+            .layout(Layout::Inline)
+            .state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="col-auto">"#,
+                r#"<label for="name" class="visually-hidden">Name</label>"#,
+                r#"<input type="text" class="form-control" name="name" id="name">"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_suffix_floating() {
+        let text = TextField::number("price", "Prix").suffix(Addon::text("€"));
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<div class="input-group">"#,
+                r#"<div class="form-floating">"#,
+                r#"<input type="number" class="form-control" name="price" id="price">"#,
+                r#"<label for="price">Prix</label>"#,
+                r#"</div>"#,
+                r#"<span class="input-group-text">€</span>"#,
+                r#"</div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_prefix_stacked_with_error() {
+        let toto = Toto {
+            id: 0,
+            code: "".to_owned(),
+        };
+
+        let validation = toto.validate().unwrap_err();
+        let text = TextField::text("id", "Login")
+            .layout(Layout::Stacked)
+            .prefix(Addon::text("@"))
+            .props(Props::default().hint("indice"))
+            .errors(&validation);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="id" class="form-label">Login</label>"#,
+                r#"<div class="input-group has-validation">"#,
+                r#"<span class="input-group-text">@</span>"#,
+                r#"<input type="text" class="form-control is-invalid" name="id" id="id" aria-invalid="true" aria-describedby="id-hint">"#,
+                r#"</div>"#,
+                r#"<div id="id-hint" class="form-text">indice</div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_floating_valid_feedback_in_group() {
+        let state = FormState::new().submitted(true);
+        let text = TextField::text("name", "Name")
+            .class("mb-3")
+            .prefix(Addon::text("@"))
+            .props(Props::default().valid_feedback("Ok").hint("indice"))
+            .state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="mb-3">"#,
+                r#"<div class="input-group has-validation">"#,
+                r#"<span class="input-group-text">@</span>"#,
+                r#"<div class="form-floating is-valid">"#,
+                r#"<input type="text" class="form-control is-valid" name="name" id="name" aria-describedby="name-hint name-feedback">"#,
+                r#"<label for="name">Name</label>"#,
+                r#"</div>"#,
+                r#"<div id="name-feedback" class="valid-feedback">Ok</div>"#,
+                r#"</div>"#,
+                r#"<div id="name-hint" class="form-text">indice</div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_plain_theme() {
+        let toto = Toto {
+            id: 0,
+            code: "".to_owned(),
+        };
+
+        let validation = toto.validate().unwrap_err();
+        let state = FormState::new().theme(Plain);
+        let text = TextField::text("id", "Name")
+            .props(Props::default().hint("indice"))
+            .errors(&validation)
+            .state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="id">Name</label>"#,
+                r#"<input type="text" class="invalid" name="id" id="id" aria-invalid="true" aria-describedby="id-hint">"#,
+                r#"<div id="id-hint" class="hint">indice</div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_tailwind_theme() {
+        let state = FormState::new().theme(Tailwind);
+        let text = TextField::text("name", "Name").state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<label for="name" class="mb-1 block text-sm font-medium text-gray-700">Name</label>"#,
+                r#"<input type="text" class="block w-full rounded-md border-gray-300 shadow-sm" name="name" id="name">"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    fn attributes<'a>(html: &'a str, name: &str) -> Vec<&'a str> {
+        html.split(&format!(r#" {name}=""#))
+            .skip(1)
+            .filter_map(|x| x.split('"').next())
+            .collect()
+    }
+
+    #[test]
+    fn test_accessibility_relations() {
+        let toto = Toto {
+            id: 0,
+            code: "".to_owned(),
+        };
+
+        let validation = toto.validate().unwrap_err();
+        let html = TextField::text("id", "Name")
+            .layout(Layout::Stacked)
+            .props(Props::default().hint("indice"))
+            .errors(&validation)
+            .render()
+            .into_string();
+
+        let ids = attributes(&html, "id");
+        let label_for = attributes(&html, "for");
+        let described_by = attributes(&html, "aria-describedby");
+
+        assert_eq!(label_for, vec![ids[0]]);
+        assert_eq!(described_by.len(), 1);
+        assert!(described_by[0].split(' ').all(|x| ids.contains(&x)));
+        assert_eq!(attributes(&html, "aria-invalid"), vec!["true"]);
+    }
+
+    #[test]
+    fn test_bind() {
+        let data = FormData::from(std::collections::HashMap::from([(
+            "name".to_owned(),
+            "Dupont".to_owned(),
+        )]));
+
+        let text = TextField::text("name", "Name")
+            .props(Props::default().placeholder("Nom"))
+            .bind(&data);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control" name="name" id="name" value="Dupont" placeholder="Nom">"#,
+                r#"<label for="name">Name</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_htmx() {
+        let text = TextField::text("q", "Recherche").htmx(
+            Htmx::new()
+                .get("/search")
+                .trigger("keyup changed delay:300ms")
+                .target("#results")
+                .swap(Swap::InnerHtml),
+        );
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r##"<input type="text" class="form-control" name="q" id="q" hx-get="/search" hx-trigger="keyup changed delay:300ms" hx-target="#results" hx-swap="innerHTML">"##,
+                r#"<label for="q">Recherche</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_htmx_inline_validation() {
+        let text = TextField::email("email", "Email")
+            .layout(Layout::Stacked)
+            .htmx(Htmx::validate("/clients/validate"));
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div id="email-field">"#,
+                r#"<label for="email" class="form-label">Email</label>"#,
+                r##"<input type="email" class="form-control" name="email" id="email" hx-post="/clients/validate" hx-trigger="change" hx-swap="outerHTML" hx-target="#email-field">"##,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_attributes() {
+        let text = TextField::text("code", "Code").props(
+            Props::default()
+                .autocomplete("off")
+                .autofocus()
+                .data("mask", r#"<"A">"#),
+        );
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control" name="code" id="code" autocomplete="off" autofocus data-mask="&lt;&quot;A&quot;&gt;">"#,
+                r#"<label for="code">Code</label>"#,
+                r#"</div>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_bind_order() {
+        let data = FormData::from_pairs(&[("ville", "Lyon")]);
+        let before = TextField::text("ville", "Ville")
+            .bind(&data)
+            .props(Props::new_value(Some("Paris")).hint("Ville de livraison"));
+        let after = TextField::text("ville", "Ville")
+            .props(Props::new_value(Some("Paris")).hint("Ville de livraison"))
+            .bind(&data);
+
+        assert_eq!(before.render().into_string(), after.render().into_string());
+        assert!(before.render().into_string().contains(r#"value="Lyon""#));
+        assert!(TextField::text("pays", "Pays")
+            .bind(&data)
+            .props(Props::new_value(Some("France")))
+            .render()
+            .into_string()
+            .contains(r#"value="France""#));
+    }
+
+    #[test]
+    fn test_display() {
+        let state = FormState::new().display(DisplayMode::List);
+        let text = TextField::text("nom", "Nom")
+            .props(Props::new_value(Some("<Dupont>")))
+            .state(&state);
+
+        assert_eq!(
+            text.render().into_string(),
+            r#"<dt>Nom</dt><dd id="nom">&lt;Dupont&gt;</dd>"#
+        );
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let villes = vec![
+            KeyValue::new("Lyon", "Lyon (69)"),
+            KeyValue::new("Lille", "Lille (59)"),
+        ];
+        let text = TextField::text("ville", "Ville").suggestions(&villes);
+
+        assert_eq!(
+            text.render().into_string(),
+            concat!(
+                r#"<div class="form-floating">"#,
+                r#"<input type="text" class="form-control" name="ville" id="ville" list="ville-list">"#,
+                r#"<datalist id="ville-list">"#,
+                r#"<option value="Lyon">Lyon (69)</option>"#,
+                r#"<option value="Lille">Lille (59)</option>"#,
+                r#"</datalist>"#,
+                r#"<label for="ville">Ville</label>"#,
                 r#"</div>"#
             )
         );
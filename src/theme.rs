@@ -0,0 +1,907 @@
+use std::{fmt::Debug, sync::Arc};
+
+use maud::{html, Markup};
+
+use crate::{
+    button::{ButtonSize, ButtonVariant},
+    layout::{join_class, Layout},
+};
+
+///Fournit les classes css et la structure html des champs
+///
+///Bootstrap5 est le thème par défaut, FormState::theme permet d'en choisir un autre à l'exécution.
+pub trait Theme: Debug + Send + Sync {
+    ///Classe du div englobant un champ
+    fn wrapper_class(&self, layout: Layout) -> &'static str;
+    fn label_class(&self, layout: Layout) -> Option<String>;
+    ///Colonne contenant le contrôle en disposition horizontale
+    fn control_column_class(&self, layout: Layout) -> Option<String>;
+    ///Colonne d'une case à cocher en disposition horizontale (décalée sous les labels)
+    fn check_column_class(&self, layout: Layout) -> Option<String>;
+
+    fn input_class(&self) -> &'static str;
+    fn select_class(&self) -> &'static str;
+    fn check_wrapper_class(&self) -> &'static str;
+    fn check_input_class(&self) -> &'static str;
+    fn check_label_class(&self) -> &'static str;
+    ///Contrôle en lecture seule du mode consultation
+    fn plaintext_class(&self) -> &'static str;
+    fn range_class(&self) -> &'static str;
+    ///Liste de propositions d'un RemoteSelect
+    fn listbox_class(&self) -> &'static str;
+    fn listbox_option_class(&self) -> &'static str;
+
+    fn invalid_class(&self) -> &'static str;
+    fn valid_class(&self) -> &'static str;
+    fn valid_feedback_class(&self) -> &'static str;
+    fn hint_class(&self) -> &'static str;
+
+    fn input_group_class(&self, has_validation: bool) -> &'static str;
+    fn input_group_text_class(&self) -> &'static str;
+
+    fn button_class(&self, variant: ButtonVariant, outline: bool, size: ButtonSize) -> String;
+    ///Lien présenté comme un bouton désactivé
+    fn disabled_link_class(&self) -> &'static str;
+    fn spinner_class(&self) -> &'static str;
+    fn button_group_class(&self, vertical: bool) -> &'static str;
+    fn button_toolbar_class(&self) -> &'static str;
+    ///Menu déroulant : conteneur, bouton, liste et éléments
+    fn dropdown_class(&self) -> &'static str;
+    fn dropdown_toggle_class(&self) -> &'static str;
+    fn dropdown_menu_class(&self) -> &'static str;
+    fn dropdown_item_class(&self) -> &'static str;
+
+    fn legend_class(&self) -> &'static str;
+    ///Contenu repliable d'un fieldset, ouvert ou non
+    fn collapse_class(&self, open: bool) -> &'static str;
+    fn collapse_toggle_class(&self) -> &'static str;
+
+    ///Étapes d'un assistant : liste, puis étape courante ou déjà faite
+    fn wizard_nav_class(&self) -> &'static str;
+    fn wizard_step_class(&self, current: bool, done: bool) -> &'static str;
+
+    ///Ligne d'un Repeater (champs et bouton de suppression)
+    fn repeater_row_class(&self) -> &'static str;
+
+    ///Tableau de liste, son conteneur défilant et la ligne affichée sans élément
+    fn table_class(&self) -> &'static str;
+    fn table_responsive_class(&self) -> &'static str;
+    fn table_empty_class(&self) -> &'static str;
+    ///Lien de tri d'un en-tête de colonne
+    fn sort_link_class(&self) -> &'static str;
+    ///Pagination : liste, puis élément courant ou désactivé
+    fn pagination_class(&self) -> &'static str;
+    fn page_item_class(&self, active: bool, disabled: bool) -> &'static str;
+    fn page_link_class(&self) -> &'static str;
+
+    ///Barre de filtres d'une liste, puis les filtres actifs retirables
+    fn filter_bar_class(&self) -> &'static str;
+    fn filter_chips_class(&self) -> &'static str;
+    fn filter_chip_class(&self) -> &'static str;
+
+    ///Disposition réellement utilisée (un thème sans label flottant se replie sur Stacked)
+    fn layout(&self, layout: Layout) -> Layout {
+        layout
+    }
+
+    ///Repli réellement utilisé par un Fieldset : il s'appuie sur le JavaScript de Bootstrap,
+    ///un autre thème affiche la section ouverte, sans bouton
+    fn collapse(&self, open: Option<bool>) -> Option<bool> {
+        open
+    }
+
+    ///Assemble label, contrôle et éléments complémentaires (feedback, hint) selon la disposition
+    fn render_field(&self, layout: Layout, parts: FieldParts) -> Markup {
+        let label_class = self.label_class(layout);
+
+        html!(
+            div id=[parts.wrapper_id] class=[join_class(self.wrapper_class(layout), parts.class)] {
+                @match layout {
+                    Layout::Floating => {
+                        (parts.control)
+                        label for=(parts.for_id) {(parts.label)}
+                        (parts.after)
+                    }
+                    Layout::Horizontal { .. } => {
+                        label for=(parts.for_id) class=[label_class] {(parts.label)}
+                        div class=[self.control_column_class(layout)] {
+                            (parts.control)
+                            (parts.after)
+                        }
+                    }
+                    Layout::Stacked | Layout::Inline => {
+                        label for=(parts.for_id) class=[label_class] {(parts.label)}
+                        (parts.control)
+                        (parts.after)
+                    }
+                }
+            }
+        )
+    }
+}
+
+///Éléments d'un champ à assembler par le thème
+#[derive(Clone, Debug, Default)]
+pub struct FieldParts<'a> {
+    ///Classes ajoutées par l'utilisateur au div englobant
+    pub class: &'a str,
+    pub wrapper_id: Option<String>,
+    pub label: &'a str,
+    pub for_id: &'a str,
+    pub control: Markup,
+    ///Feedback et hint
+    pub after: Markup,
+}
+
+///Thème par défaut (Bootstrap5), le même quelles que soient les features activées
+pub fn default_theme() -> Arc<dyn Theme> {
+    Arc::new(Bootstrap5)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bootstrap5;
+
+impl Theme for Bootstrap5 {
+    fn wrapper_class(&self, layout: Layout) -> &'static str {
+        match layout {
+            Layout::Floating => "form-floating",
+            Layout::Stacked => "",
+            Layout::Horizontal { .. } => "row",
+            Layout::Inline => "col-auto",
+        }
+    }
+
+    fn label_class(&self, layout: Layout) -> Option<String> {
+        match layout {
+            Layout::Floating => None,
+            Layout::Stacked => Some("form-label".to_owned()),
+            Layout::Horizontal { label, .. } => Some(format!("col-sm-{label} col-form-label")),
+            Layout::Inline => Some("visually-hidden".to_owned()),
+        }
+    }
+
+    fn control_column_class(&self, layout: Layout) -> Option<String> {
+        match layout {
+            Layout::Horizontal { control, .. } => Some(format!("col-sm-{control}")),
+            _ => None,
+        }
+    }
+
+    fn check_column_class(&self, layout: Layout) -> Option<String> {
+        match layout {
+            Layout::Horizontal { label, control } => {
+                Some(format!("col-sm-{control} offset-sm-{label}"))
+            }
+            _ => None,
+        }
+    }
+
+    fn input_class(&self) -> &'static str {
+        "form-control"
+    }
+
+    fn select_class(&self) -> &'static str {
+        "form-select"
+    }
+
+    fn check_wrapper_class(&self) -> &'static str {
+        "form-check"
+    }
+
+    fn check_input_class(&self) -> &'static str {
+        "form-check-input"
+    }
+
+    fn check_label_class(&self) -> &'static str {
+        "form-check-label"
+    }
+
+    fn plaintext_class(&self) -> &'static str {
+        "form-control-plaintext"
+    }
+
+    fn range_class(&self) -> &'static str {
+        "form-range"
+    }
+
+    fn listbox_class(&self) -> &'static str {
+        "list-group"
+    }
+
+    fn listbox_option_class(&self) -> &'static str {
+        "list-group-item list-group-item-action"
+    }
+
+    fn invalid_class(&self) -> &'static str {
+        "is-invalid"
+    }
+
+    fn valid_class(&self) -> &'static str {
+        "is-valid"
+    }
+
+    fn valid_feedback_class(&self) -> &'static str {
+        "valid-feedback"
+    }
+
+    fn hint_class(&self) -> &'static str {
+        "form-text"
+    }
+
+    fn input_group_class(&self, has_validation: bool) -> &'static str {
+        match has_validation {
+            true => "input-group has-validation",
+            false => "input-group",
+        }
+    }
+
+    fn input_group_text_class(&self) -> &'static str {
+        "input-group-text"
+    }
+
+    fn button_class(&self, variant: ButtonVariant, outline: bool, size: ButtonSize) -> String {
+        let variant = match (variant, outline) {
+            (ButtonVariant::Primary, false) => "btn-primary",
+            (ButtonVariant::Secondary, false) => "btn-secondary",
+            (ButtonVariant::Danger, false) => "btn-danger",
+            (ButtonVariant::Primary, true) => "btn-outline-primary",
+            (ButtonVariant::Secondary, true) => "btn-outline-secondary",
+            (ButtonVariant::Danger, true) => "btn-outline-danger",
+            (ButtonVariant::Link, _) => "btn-link",
+        };
+        let size = match size {
+            ButtonSize::Small => " btn-sm",
+            ButtonSize::Normal => "",
+            ButtonSize::Large => " btn-lg",
+        };
+
+        format!("btn {variant}{size}")
+    }
+
+    fn disabled_link_class(&self) -> &'static str {
+        "disabled"
+    }
+
+    fn spinner_class(&self) -> &'static str {
+        "spinner-border spinner-border-sm"
+    }
+
+    fn button_group_class(&self, vertical: bool) -> &'static str {
+        match vertical {
+            true => "btn-group-vertical",
+            false => "btn-group",
+        }
+    }
+
+    fn button_toolbar_class(&self) -> &'static str {
+        "btn-toolbar"
+    }
+
+    fn dropdown_class(&self) -> &'static str {
+        "dropdown"
+    }
+
+    fn dropdown_toggle_class(&self) -> &'static str {
+        "dropdown-toggle"
+    }
+
+    fn dropdown_menu_class(&self) -> &'static str {
+        "dropdown-menu"
+    }
+
+    fn dropdown_item_class(&self) -> &'static str {
+        "dropdown-item"
+    }
+
+    fn legend_class(&self) -> &'static str {
+        "fs-5"
+    }
+
+    fn collapse_class(&self, open: bool) -> &'static str {
+        match open {
+            true => "collapse show",
+            false => "collapse",
+        }
+    }
+
+    fn collapse_toggle_class(&self) -> &'static str {
+        "btn btn-link p-0 text-decoration-none"
+    }
+
+    fn wizard_nav_class(&self) -> &'static str {
+        "nav nav-pills mb-3"
+    }
+
+    fn wizard_step_class(&self, current: bool, done: bool) -> &'static str {
+        match (current, done) {
+            (true, _) => "nav-link active",
+            (false, true) => "nav-link",
+            (false, false) => "nav-link disabled",
+        }
+    }
+
+    fn repeater_row_class(&self) -> &'static str {
+        "d-flex gap-2 align-items-end mb-2"
+    }
+
+    fn table_class(&self) -> &'static str {
+        "table"
+    }
+
+    fn table_responsive_class(&self) -> &'static str {
+        "table-responsive"
+    }
+
+    fn table_empty_class(&self) -> &'static str {
+        "text-center text-body-secondary"
+    }
+
+    fn sort_link_class(&self) -> &'static str {
+        "link-body-emphasis text-decoration-none"
+    }
+
+    fn pagination_class(&self) -> &'static str {
+        "pagination"
+    }
+
+    fn page_item_class(&self, active: bool, disabled: bool) -> &'static str {
+        match (active, disabled) {
+            (true, _) => "page-item active",
+            (false, true) => "page-item disabled",
+            (false, false) => "page-item",
+        }
+    }
+
+    fn page_link_class(&self) -> &'static str {
+        "page-link"
+    }
+
+    fn filter_bar_class(&self) -> &'static str {
+        "row g-2 align-items-end mb-3"
+    }
+
+    fn filter_chips_class(&self) -> &'static str {
+        "d-flex flex-wrap gap-2 align-items-center mb-3"
+    }
+
+    fn filter_chip_class(&self) -> &'static str {
+        "badge rounded-pill text-bg-light border text-decoration-none"
+    }
+}
+
+///Html sémantique, sans framework : seules quelques classes d'état sont conservées
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Plain;
+
+impl Theme for Plain {
+    fn wrapper_class(&self, _layout: Layout) -> &'static str {
+        ""
+    }
+
+    fn label_class(&self, _layout: Layout) -> Option<String> {
+        None
+    }
+
+    fn control_column_class(&self, _layout: Layout) -> Option<String> {
+        None
+    }
+
+    fn check_column_class(&self, _layout: Layout) -> Option<String> {
+        None
+    }
+
+    fn input_class(&self) -> &'static str {
+        ""
+    }
+
+    fn select_class(&self) -> &'static str {
+        ""
+    }
+
+    fn check_wrapper_class(&self) -> &'static str {
+        ""
+    }
+
+    fn check_input_class(&self) -> &'static str {
+        ""
+    }
+
+    fn check_label_class(&self) -> &'static str {
+        ""
+    }
+
+    fn plaintext_class(&self) -> &'static str {
+        ""
+    }
+
+    fn range_class(&self) -> &'static str {
+        ""
+    }
+
+    fn listbox_class(&self) -> &'static str {
+        ""
+    }
+
+    fn listbox_option_class(&self) -> &'static str {
+        ""
+    }
+
+    fn invalid_class(&self) -> &'static str {
+        "invalid"
+    }
+
+    fn valid_class(&self) -> &'static str {
+        "valid"
+    }
+
+    fn valid_feedback_class(&self) -> &'static str {
+        "feedback"
+    }
+
+    fn hint_class(&self) -> &'static str {
+        "hint"
+    }
+
+    fn input_group_class(&self, _has_validation: bool) -> &'static str {
+        ""
+    }
+
+    fn input_group_text_class(&self) -> &'static str {
+        ""
+    }
+
+    fn button_class(&self, _variant: ButtonVariant, _outline: bool, _size: ButtonSize) -> String {
+        String::new()
+    }
+
+    fn disabled_link_class(&self) -> &'static str {
+        ""
+    }
+
+    fn spinner_class(&self) -> &'static str {
+        ""
+    }
+
+    fn button_group_class(&self, _vertical: bool) -> &'static str {
+        ""
+    }
+
+    fn button_toolbar_class(&self) -> &'static str {
+        ""
+    }
+
+    fn dropdown_class(&self) -> &'static str {
+        ""
+    }
+
+    fn dropdown_toggle_class(&self) -> &'static str {
+        ""
+    }
+
+    fn dropdown_menu_class(&self) -> &'static str {
+        ""
+    }
+
+    fn dropdown_item_class(&self) -> &'static str {
+        ""
+    }
+
+    fn legend_class(&self) -> &'static str {
+        ""
+    }
+
+    fn collapse_class(&self, _open: bool) -> &'static str {
+        ""
+    }
+
+    fn collapse_toggle_class(&self) -> &'static str {
+        ""
+    }
+
+    fn wizard_nav_class(&self) -> &'static str {
+        ""
+    }
+
+    fn wizard_step_class(&self, _current: bool, _done: bool) -> &'static str {
+        ""
+    }
+
+    fn repeater_row_class(&self) -> &'static str {
+        ""
+    }
+
+    fn table_class(&self) -> &'static str {
+        ""
+    }
+
+    fn table_responsive_class(&self) -> &'static str {
+        ""
+    }
+
+    fn table_empty_class(&self) -> &'static str {
+        ""
+    }
+
+    fn sort_link_class(&self) -> &'static str {
+        ""
+    }
+
+    fn pagination_class(&self) -> &'static str {
+        ""
+    }
+
+    fn page_item_class(&self, _active: bool, _disabled: bool) -> &'static str {
+        ""
+    }
+
+    fn page_link_class(&self) -> &'static str {
+        ""
+    }
+
+    fn filter_bar_class(&self) -> &'static str {
+        ""
+    }
+
+    fn filter_chips_class(&self) -> &'static str {
+        ""
+    }
+
+    fn filter_chip_class(&self) -> &'static str {
+        ""
+    }
+
+    fn layout(&self, _layout: Layout) -> Layout {
+        Layout::Stacked
+    }
+
+    fn collapse(&self, _open: Option<bool>) -> Option<bool> {
+        None
+    }
+}
+
+///Classes utilitaires Tailwind (prévu pour le plugin @tailwindcss/forms)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tailwind;
+
+//Classes écrites en entier : Tailwind ne garde que celles qu'il trouve dans les sources
+fn tailwind_col_span(columns: u8) -> &'static str {
+    match columns {
+        1 => "col-span-1",
+        2 => "col-span-2",
+        3 => "col-span-3",
+        4 => "col-span-4",
+        5 => "col-span-5",
+        6 => "col-span-6",
+        7 => "col-span-7",
+        8 => "col-span-8",
+        9 => "col-span-9",
+        10 => "col-span-10",
+        11 => "col-span-11",
+        _ => "col-span-12",
+    }
+}
+
+fn tailwind_col_start(column: u8) -> &'static str {
+    match column {
+        0 | 1 => "col-start-1",
+        2 => "col-start-2",
+        3 => "col-start-3",
+        4 => "col-start-4",
+        5 => "col-start-5",
+        6 => "col-start-6",
+        7 => "col-start-7",
+        8 => "col-start-8",
+        9 => "col-start-9",
+        10 => "col-start-10",
+        11 => "col-start-11",
+        12 => "col-start-12",
+        _ => "col-start-13",
+    }
+}
+
+impl Theme for Tailwind {
+    fn wrapper_class(&self, layout: Layout) -> &'static str {
+        match layout {
+            Layout::Floating | Layout::Stacked => "",
+            Layout::Horizontal { .. } => "grid grid-cols-12 items-center gap-4",
+            Layout::Inline => "flex-none",
+        }
+    }
+
+    fn label_class(&self, layout: Layout) -> Option<String> {
+        match layout {
+            Layout::Floating | Layout::Stacked => {
+                Some("mb-1 block text-sm font-medium text-gray-700".to_owned())
+            }
+            Layout::Horizontal { label, .. } => Some(format!(
+                "{} text-sm font-medium text-gray-700",
+                tailwind_col_span(label)
+            )),
+            Layout::Inline => Some("sr-only".to_owned()),
+        }
+    }
+
+    fn control_column_class(&self, layout: Layout) -> Option<String> {
+        match layout {
+            Layout::Horizontal { control, .. } => Some(tailwind_col_span(control).to_owned()),
+            _ => None,
+        }
+    }
+
+    fn check_column_class(&self, layout: Layout) -> Option<String> {
+        match layout {
+            Layout::Horizontal { label, control } => Some(format!(
+                "{} {}",
+                tailwind_col_span(control),
+                tailwind_col_start(label.saturating_add(1))
+            )),
+            _ => None,
+        }
+    }
+
+    fn input_class(&self) -> &'static str {
+        "block w-full rounded-md border-gray-300 shadow-sm"
+    }
+
+    fn select_class(&self) -> &'static str {
+        "block w-full rounded-md border-gray-300 shadow-sm"
+    }
+
+    fn check_wrapper_class(&self) -> &'static str {
+        "flex items-center gap-2"
+    }
+
+    fn check_input_class(&self) -> &'static str {
+        "h-4 w-4 rounded border-gray-300"
+    }
+
+    fn check_label_class(&self) -> &'static str {
+        "text-sm text-gray-700"
+    }
+
+    fn plaintext_class(&self) -> &'static str {
+        "block w-full py-2 text-sm text-gray-900"
+    }
+
+    fn range_class(&self) -> &'static str {
+        "w-full accent-indigo-600"
+    }
+
+    fn listbox_class(&self) -> &'static str {
+        "mt-1 rounded-md border border-gray-200 bg-white shadow-sm"
+    }
+
+    fn listbox_option_class(&self) -> &'static str {
+        "block w-full px-3 py-2 text-left text-sm hover:bg-gray-100"
+    }
+
+    fn invalid_class(&self) -> &'static str {
+        "border-red-500"
+    }
+
+    fn valid_class(&self) -> &'static str {
+        "border-green-500"
+    }
+
+    fn valid_feedback_class(&self) -> &'static str {
+        "mt-1 text-sm text-green-600"
+    }
+
+    fn hint_class(&self) -> &'static str {
+        "mt-1 text-sm text-gray-500"
+    }
+
+    fn input_group_class(&self, _has_validation: bool) -> &'static str {
+        "flex rounded-md shadow-sm"
+    }
+
+    fn input_group_text_class(&self) -> &'static str {
+        "inline-flex items-center border border-gray-300 bg-gray-50 px-3 text-sm text-gray-500"
+    }
+
+    fn button_class(&self, variant: ButtonVariant, outline: bool, size: ButtonSize) -> String {
+        let variant = match (variant, outline) {
+            (ButtonVariant::Primary, false) => "bg-indigo-600 text-white hover:bg-indigo-500",
+            (ButtonVariant::Secondary, false) => "bg-gray-600 text-white hover:bg-gray-500",
+            (ButtonVariant::Danger, false) => "bg-red-600 text-white hover:bg-red-500",
+            (ButtonVariant::Primary, true) => {
+                "border border-indigo-600 text-indigo-600 hover:bg-indigo-50"
+            }
+            (ButtonVariant::Secondary, true) => {
+                "border border-gray-300 text-gray-700 hover:bg-gray-50"
+            }
+            (ButtonVariant::Danger, true) => "border border-red-600 text-red-600 hover:bg-red-50",
+            (ButtonVariant::Link, _) => "text-indigo-600 hover:underline",
+        };
+        let size = match size {
+            ButtonSize::Small => "px-2 py-1 text-xs",
+            ButtonSize::Normal => "px-3 py-2 text-sm",
+            ButtonSize::Large => "px-4 py-3 text-base",
+        };
+
+        format!("inline-flex items-center gap-2 rounded-md font-semibold {size} {variant}")
+    }
+
+    fn disabled_link_class(&self) -> &'static str {
+        "pointer-events-none opacity-50"
+    }
+
+    fn spinner_class(&self) -> &'static str {
+        "h-4 w-4 animate-spin rounded-full border-2 border-current border-t-transparent"
+    }
+
+    fn button_group_class(&self, vertical: bool) -> &'static str {
+        match vertical {
+            true => "inline-flex flex-col",
+            false => "inline-flex",
+        }
+    }
+
+    fn button_toolbar_class(&self) -> &'static str {
+        "flex flex-wrap gap-2"
+    }
+
+    fn dropdown_class(&self) -> &'static str {
+        "relative inline-block"
+    }
+
+    fn dropdown_toggle_class(&self) -> &'static str {
+        ""
+    }
+
+    fn dropdown_menu_class(&self) -> &'static str {
+        "absolute z-10 mt-1 min-w-max rounded-md bg-white py-1 shadow-lg"
+    }
+
+    fn dropdown_item_class(&self) -> &'static str {
+        "block px-4 py-2 text-sm text-gray-700 hover:bg-gray-100"
+    }
+
+    fn legend_class(&self) -> &'static str {
+        "text-base font-semibold text-gray-900"
+    }
+
+    fn collapse_class(&self, _open: bool) -> &'static str {
+        ""
+    }
+
+    fn collapse_toggle_class(&self) -> &'static str {
+        "text-left font-semibold text-gray-900"
+    }
+
+    fn wizard_nav_class(&self) -> &'static str {
+        "mb-4 flex gap-4 text-sm"
+    }
+
+    fn wizard_step_class(&self, current: bool, done: bool) -> &'static str {
+        match (current, done) {
+            (true, _) => "font-semibold text-indigo-600",
+            (false, true) => "text-gray-700",
+            (false, false) => "text-gray-400",
+        }
+    }
+
+    fn repeater_row_class(&self) -> &'static str {
+        "mb-2 flex items-end gap-2"
+    }
+
+    fn table_class(&self) -> &'static str {
+        "min-w-full divide-y divide-gray-200 text-sm"
+    }
+
+    fn table_responsive_class(&self) -> &'static str {
+        "overflow-x-auto"
+    }
+
+    fn table_empty_class(&self) -> &'static str {
+        "py-4 text-center text-gray-500"
+    }
+
+    fn sort_link_class(&self) -> &'static str {
+        "inline-flex items-center gap-1 hover:underline"
+    }
+
+    fn pagination_class(&self) -> &'static str {
+        "flex gap-1"
+    }
+
+    fn page_item_class(&self, active: bool, disabled: bool) -> &'static str {
+        match (active, disabled) {
+            (true, _) => "rounded bg-indigo-600 text-white",
+            (false, true) => "pointer-events-none text-gray-400",
+            (false, false) => "rounded text-gray-700 hover:bg-gray-100",
+        }
+    }
+
+    fn page_link_class(&self) -> &'static str {
+        "block px-3 py-1"
+    }
+
+    fn filter_bar_class(&self) -> &'static str {
+        "mb-4 flex flex-wrap items-end gap-2"
+    }
+
+    fn filter_chips_class(&self) -> &'static str {
+        "mb-4 flex flex-wrap items-center gap-2"
+    }
+
+    fn filter_chip_class(&self) -> &'static str {
+        "rounded-full bg-gray-100 px-3 py-1 text-sm text-gray-700 hover:bg-gray-200"
+    }
+
+    fn layout(&self, layout: Layout) -> Layout {
+        match layout {
+            Layout::Floating => Layout::Stacked,
+            _ => layout,
+        }
+    }
+
+    fn collapse(&self, _open: Option<bool>) -> Option<bool> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bootstrap_wrapper_class() {
+        let theme = Bootstrap5;
+
+        assert_eq!(
+            join_class(theme.wrapper_class(Layout::Floating), "mb-3"),
+            Some("form-floating mb-3".to_owned())
+        );
+        assert_eq!(join_class(theme.wrapper_class(Layout::Stacked), ""), None);
+        assert_eq!(
+            join_class(theme.wrapper_class(Layout::horizontal(2, 10)), ""),
+            Some("row".to_owned())
+        );
+    }
+
+    #[test]
+    fn bootstrap_horizontal_classes() {
+        let theme = Bootstrap5;
+        let layout = Layout::horizontal(3, 9);
+
+        assert_eq!(
+            theme.label_class(layout),
+            Some("col-sm-3 col-form-label".to_owned())
+        );
+        assert_eq!(
+            theme.control_column_class(layout),
+            Some("col-sm-9".to_owned())
+        );
+        assert_eq!(
+            theme.check_column_class(layout),
+            Some("col-sm-9 offset-sm-3".to_owned())
+        );
+    }
+
+    #[test]
+    fn floating_fallback() {
+        assert_eq!(Bootstrap5.layout(Layout::Floating), Layout::Floating);
+        assert_eq!(Plain.layout(Layout::horizontal(2, 10)), Layout::Stacked);
+        assert_eq!(Tailwind.layout(Layout::Floating), Layout::Stacked);
+        assert_eq!(Tailwind.layout(Layout::Inline), Layout::Inline);
+    }
+
+    #[test]
+    fn tailwind_horizontal_classes() {
+        let layout = Layout::horizontal(3, 9);
+
+        assert_eq!(
+            Tailwind.check_column_class(layout),
+            Some("col-span-9 col-start-4".to_owned())
+        );
+        assert_eq!(
+            Tailwind.label_class(layout),
+            Some("col-span-3 text-sm font-medium text-gray-700".to_owned())
+        );
+        assert_eq!(
+            Tailwind.control_column_class(Layout::horizontal(2, 40)),
+            Some("col-span-12".to_owned())
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    form_data::FormData,
+    schema::{parse_urlencoded, SchemaError},
+};
+
+///Extracteur axum : formulaire urlencoded désérialisé puis validé
+///
+///Le handler prend `Result<ValidatedForm<T>, FormRejection>` pour ré-afficher la page
+///avec les valeurs saisies et les erreurs quand la validation échoue. Une valeur que serde
+///refuse (texte dans un champ numérique, champ manquant) est une erreur du champ concerné.
+#[derive(Clone, Debug)]
+pub struct ValidatedForm<T>(pub T);
+
+///Formulaire refusé par serde ou Validate, avec les valeurs telles que soumises
+#[derive(Clone, Debug)]
+pub struct InvalidForm {
+    pub data: FormData,
+    pub errors: ValidationErrors,
+}
+
+#[derive(Debug)]
+pub enum FormRejection {
+    ///Corps illisible ou incompatible avec T sans erreur rattachable à un champ
+    BadRequest(String),
+    Invalid(InvalidForm),
+}
+
+impl fmt::Display for FormRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadRequest(message) => write!(f, "formulaire illisible : {message}"),
+            Self::Invalid(invalid) => write!(f, "formulaire invalide : {}", invalid.errors),
+        }
+    }
+}
+
+impl std::error::Error for FormRejection {}
+
+impl IntoResponse for FormRejection {
+    fn into_response(self) -> Response {
+        let status = match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Invalid(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+
+        (status, self.to_string()).into_response()
+    }
+}
+
+impl<T, S> FromRequest<S> for ValidatedForm<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = FormRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| FormRejection::BadRequest(e.body_text()))?;
+
+        let body = String::from_utf8_lossy(&body);
+        let errors = match parse_urlencoded::<T>(&body) {
+            Ok(value) => match value.validate() {
+                Ok(()) => return Ok(Self(value)),
+                Err(errors) => errors,
+            },
+            Err(SchemaError::Invalid(errors)) => errors,
+            Err(e) => return Err(FormRejection::BadRequest(e.to_string())),
+        };
+        let data = FormData::from_urlencoded(&body)
+            .map_err(|e| FormRejection::BadRequest(e.to_string()))?;
+
+        Err(FormRejection::Invalid(InvalidForm { data, errors }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::header, routing::post, Router};
+    use http_body_util::BodyExt;
+    use maud::{html, Markup, Render};
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use crate::text::TextField;
+
+    use super::*;
+
+    #[derive(Deserialize, Validate)]
+    struct Client {
+        #[validate(length(min = 2))]
+        nom: String,
+        age: Option<u32>,
+    }
+
+    async fn save(form: Result<ValidatedForm<Client>, FormRejection>) -> Response {
+        match form {
+            Ok(ValidatedForm(client)) => {
+                format!("ok {} {:?}", client.nom, client.age).into_response()
+            }
+            Err(FormRejection::Invalid(InvalidForm { data, errors })) => {
+                let page: Markup = html!((TextField::text("nom", "Nom")
+                    .bind(&data)
+                    .errors(&errors)
+                    .render())(
+                    TextField::number("age", "Âge")
+                        .bind(&data)
+                        .errors(&errors)
+                        .render()
+                ));
+
+                (StatusCode::UNPROCESSABLE_ENTITY, page).into_response()
+            }
+            Err(rejection) => rejection.into_response(),
+        }
+    }
+
+    async fn post_form(body: &'static str) -> (StatusCode, String) {
+        let app = Router::new().route("/", post(save));
+        let request = Request::post("/")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn valid() {
+        assert_eq!(
+            post_form("nom=Dupont").await,
+            (StatusCode::OK, "ok Dupont None".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_rerendered() {
+        let (status, body) = post_form("nom=D").await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains(r#"class="form-control is-invalid" name="nom" id="nom" value="D""#));
+    }
+
+    #[tokio::test]
+    async fn not_a_number() {
+        let (status, body) = post_form("nom=Dupont&age=abc").await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains(r#"name="nom" id="nom" value="Dupont""#));
+        assert!(body.contains(
+            r#"class="form-control is-invalid" name="age" id="age" value="abc" aria-invalid="true""#
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_field() {
+        let (status, body) = post_form("autre=1").await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains(r#"class="form-control is-invalid" name="nom" id="nom""#));
+    }
+
+    #[tokio::test]
+    async fn bad_request() {
+        let (status, _) = post_form("nom=Dupont&nom=Martin").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,578 @@
+use std::fmt;
+
+use maud::{html, Markup, Render};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    button::{Button, ButtonGroup, ButtonVariant},
+    csrf::{CsrfKey, CSRF_FIELD},
+    form::{Form, FormState},
+    form_data::FormData,
+    inline::field_errors,
+    layout::join_class,
+    schema::{parse_urlencoded, FormSchema, SchemaError},
+};
+
+///Champ caché portant l'étape affichée (à partir de 0)
+pub const WIZARD_STEP: &str = "_wizard_step";
+///Champ caché portant la signature des valeurs reprises
+pub const WIZARD_SIGNATURE: &str = "_wizard_signature";
+///name des boutons précédent/suivant
+pub const WIZARD_NAV: &str = "_wizard_nav";
+
+const SIGNATURE_CONTEXT: &str = "wizard";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WizardError {
+    ///Assistant signé mais signature absente de la soumission
+    MissingSignature,
+    ///Valeurs reprises modifiées dans le navigateur, ou signature illisible
+    BadSignature,
+}
+
+impl fmt::Display for WizardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::MissingSignature => "signature de l'assistant absente",
+            Self::BadSignature => "valeurs de l'assistant modifiées",
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for WizardError {}
+
+///Étape d'un assistant : titre et names des champs saisis à cette étape
+#[derive(Clone, Debug, Default)]
+pub struct WizardStep {
+    title: String,
+    fields: Vec<String>,
+}
+
+impl WizardStep {
+    pub fn new(title: &str, fields: &[&str]) -> Self {
+        Self {
+            title: title.to_owned(),
+            fields: fields.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    ///name du champ ou d'un de ses éléments (`tags[0]`, `adresse.ville`)
+    fn owns(&self, name: &str) -> bool {
+        self.fields.iter().any(|field| {
+            name.strip_prefix(field.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['[', '.']))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WizardNav {
+    Prev,
+    #[default]
+    Next,
+    Finish,
+}
+
+impl WizardNav {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Prev => "prev",
+            Self::Next => "next",
+            Self::Finish => "finish",
+        }
+    }
+}
+
+///Étape soumise, bouton utilisé et valeurs (étapes précédentes comprises)
+#[derive(Clone, Debug, Default)]
+pub struct WizardSubmission {
+    pub step: usize,
+    pub nav: WizardNav,
+    pub data: FormData,
+}
+
+impl WizardSubmission {
+    pub fn from_urlencoded(body: &str) -> Result<Self, serde_urlencoded::de::Error> {
+        let data = FormData::from_urlencoded(body)?;
+        let step = data
+            .get(WIZARD_STEP)
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default();
+        let nav = match data.get(WIZARD_NAV) {
+            Some("prev") => WizardNav::Prev,
+            Some("finish") => WizardNav::Finish,
+            _ => WizardNav::Next,
+        };
+
+        Ok(Self { step, nav, data })
+    }
+
+    ///Lecture et validation du modèle complet (dernière étape) au travers de schema
+    ///
+    ///Les valeurs reprises sont relues comme FormData : un `Vec` repris en `tags[0]`, `tags[1]`
+    ///est reconstruit, ce que serde_urlencoded ne sait pas faire.
+    pub fn parse<T>(&self, schema: &FormSchema) -> Result<T, SchemaError>
+    where
+        T: DeserializeOwned + Validate,
+    {
+        let value = schema.parse_data::<T>(&self.data)?;
+        value.validate().map_err(SchemaError::Invalid)?;
+
+        Ok(value)
+    }
+}
+
+///Formulaire en plusieurs étapes
+///
+///Chaque étape ne rend que ses champs, les valeurs des autres étapes sont reprises
+///en champs cachés, signés si une clé est donnée.
+#[derive(Clone, Debug, Default)]
+pub struct Wizard {
+    action: String,
+    steps: Vec<WizardStep>,
+    current: usize,
+    data: FormData,
+    key: Option<CsrfKey>,
+    items: Vec<Markup>,
+    prev_label: String,
+    next_label: String,
+    finish_label: String,
+    state: FormState,
+}
+
+impl Wizard {
+    pub fn new(action: &str, steps: Vec<WizardStep>) -> Self {
+        Self {
+            action: action.to_owned(),
+            steps,
+            prev_label: "Précédent".to_owned(),
+            next_label: "Suivant".to_owned(),
+            finish_label: "Terminer".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    ///Étape affichée (à partir de 0)
+    pub fn current(self, step: usize) -> Self {
+        Self {
+            current: step.min(self.steps.len().saturating_sub(1)),
+            ..self
+        }
+    }
+
+    ///Valeurs saisies jusqu'ici
+    pub fn data(self, data: &FormData) -> Self {
+        Self {
+            data: data.clone(),
+            ..self
+        }
+    }
+
+    ///Signe les valeurs reprises pour refuser celles modifiées dans le navigateur
+    pub fn signed(self, key: &CsrfKey) -> Self {
+        Self {
+            key: Some(key.clone()),
+            ..self
+        }
+    }
+
+    ///Champs de l'étape affichée
+    pub fn push<R: Render>(self, item: R) -> Self {
+        let mut items = self.items;
+        items.push(item.render());
+
+        Self { items, ..self }
+    }
+
+    ///Libellé du bouton de retour ("Précédent")
+    pub fn prev_label(self, label: &str) -> Self {
+        Self {
+            prev_label: label.to_owned(),
+            ..self
+        }
+    }
+
+    ///Libellé du bouton passant à l'étape suivante ("Suivant")
+    pub fn next_label(self, label: &str) -> Self {
+        Self {
+            next_label: label.to_owned(),
+            ..self
+        }
+    }
+
+    ///Libellé du bouton de la dernière étape ("Terminer")
+    pub fn finish_label(self, label: &str) -> Self {
+        Self {
+            finish_label: label.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn state(self, state: &FormState) -> Self {
+        Self {
+            state: state.clone(),
+            ..self
+        }
+    }
+
+    pub fn is_last(&self, step: usize) -> bool {
+        step + 1 >= self.steps.len()
+    }
+
+    ///Étape à afficher après la soumission (avant validation)
+    pub fn target(&self, submission: &WizardSubmission) -> usize {
+        match submission.nav {
+            WizardNav::Prev => submission.step.saturating_sub(1),
+            WizardNav::Next => (submission.step + 1).min(self.steps.len().saturating_sub(1)),
+            WizardNav::Finish => submission.step,
+        }
+    }
+
+    ///Vérifie que les valeurs reprises des autres étapes n'ont pas été modifiées
+    pub fn verify(&self, submission: &WizardSubmission) -> Result<(), WizardError> {
+        let Some(key) = &self.key else {
+            return Ok(());
+        };
+        let signature = submission
+            .data
+            .get(WIZARD_SIGNATURE)
+            .ok_or(WizardError::MissingSignature)?;
+        let message = self.message(submission.step, &submission.data);
+
+        key.verify_signature(SIGNATURE_CONTEXT, message.as_bytes(), signature)
+            .map_err(|_| WizardError::BadSignature)
+    }
+
+    ///Erreurs limitées aux champs d'une étape (Validate sur le modèle complet)
+    pub fn step_errors(&self, step: usize, errors: &ValidationErrors) -> ValidationErrors {
+        let mut result = ValidationErrors::new();
+
+        for field in self
+            .steps
+            .get(step)
+            .map(|x| x.fields.as_slice())
+            .unwrap_or_default()
+        {
+            for (name, list) in field_errors(errors, field).field_errors() {
+                for error in list {
+                    result.add(name, error.clone());
+                }
+            }
+        }
+
+        result
+    }
+
+    ///Lecture et validation d'une structure propre à l'étape, les autres champs sont ignorés
+    ///
+    ///Une étape avec un champ multiple passe par WizardSubmission::parse.
+    pub fn parse_step<T>(body: &str) -> Result<T, SchemaError>
+    where
+        T: DeserializeOwned + Validate,
+    {
+        let value = parse_urlencoded::<T>(body)?;
+        value.validate().map_err(SchemaError::Invalid)?;
+
+        Ok(value)
+    }
+
+    ///Valeurs à reprendre en champs cachés : tout sauf l'étape affichée et les champs techniques
+    fn carried<'a>(&self, step: usize, data: &'a FormData) -> Vec<(&'a str, &'a str)> {
+        let entries = data.entries();
+        let is_indexed = |name: &str| {
+            let indexed = format!("{name}[0]");
+            entries.iter().any(|(key, _)| *key == indexed)
+        };
+
+        entries
+            .iter()
+            .copied()
+            .filter(|(name, _)| {
+                ![WIZARD_STEP, WIZARD_SIGNATURE, WIZARD_NAV, CSRF_FIELD].contains(name)
+            })
+            .filter(|(name, _)| !self.steps.get(step).is_some_and(|x| x.owns(name)))
+            //un name répété est déjà repris par ses valeurs indexées
+            .filter(|(name, _)| !is_indexed(name))
+            .collect()
+    }
+
+    fn message(&self, step: usize, data: &FormData) -> String {
+        let mut pairs = vec![(WIZARD_STEP, step.to_string())];
+        pairs.extend(
+            self.carried(step, data)
+                .into_iter()
+                .map(|(name, value)| (name, value.to_owned())),
+        );
+
+        serde_urlencoded::to_string(pairs).unwrap_or_default()
+    }
+}
+
+impl Render for Wizard {
+    fn render(&self) -> Markup {
+        let theme = self.state.theme.as_ref();
+        let signature = self.key.as_ref().map(|key| {
+            key.sign(
+                SIGNATURE_CONTEXT,
+                self.message(self.current, &self.data).as_bytes(),
+            )
+        });
+
+        let prev = Button::submit(&self.prev_label)
+            .variant(ButtonVariant::Secondary)
+            .name_value(WIZARD_NAV, WizardNav::Prev.as_str())
+            .flag("formnovalidate")
+            .state(&self.state);
+        let next = match self.is_last(self.current) {
+            true => Button::submit(&self.finish_label)
+                .name_value(WIZARD_NAV, WizardNav::Finish.as_str()),
+            false => {
+                Button::submit(&self.next_label).name_value(WIZARD_NAV, WizardNav::Next.as_str())
+            }
+        }
+        .state(&self.state);
+        let footer = match self.current {
+            0 => ButtonGroup::toolbar().push(next),
+            _ => ButtonGroup::toolbar().push(prev).push(next),
+        }
+        .state(&self.state);
+
+        let content = html!(
+                ol class=[join_class(theme.wizard_nav_class(), "")] {
+                    @for (i, step) in self.steps.iter().enumerate() {
+                        li
+                            class=[join_class(theme.wizard_step_class(i == self.current, i < self.current), "")]
+                            aria-current=[(i == self.current).then_some("step")] {
+                            (i + 1) ". " (step.title)
+                        }
+                    }
+                }
+                input type="hidden" name=(WIZARD_STEP) value=(self.current);
+                @for (name, value) in self.carried(self.current, &self.data) {
+                    input type="hidden" name=(name) value=(value);
+                }
+                @if let Some(signature) = &signature {
+                    input type="hidden" name=(WIZARD_SIGNATURE) value=(signature);
+                }
+                @for item in &self.items {
+                    (item)
+                }
+                (footer)
+        );
+
+        Form::post(&self.action).push(content).render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{key_value::KeyValue, schema::FieldSchema, text::TextField};
+
+    use super::*;
+
+    fn wizard() -> Wizard {
+        Wizard::new(
+            "/inscription",
+            vec![
+                WizardStep::new("Identité", &["nom"]),
+                WizardStep::new("Contact", &["email", "tags"]),
+                WizardStep::new("Fin", &[]),
+            ],
+        )
+    }
+
+    #[derive(Deserialize, Validate)]
+    struct Contact {
+        #[validate(email)]
+        email: String,
+    }
+
+    #[test]
+    fn render_step() {
+        let data = FormData::from_pairs(&[("nom", "Dupont"), ("email", "a@b.fr")]);
+        let wizard = wizard()
+            .current(1)
+            .data(&data)
+            .push(TextField::email("email", "Email").bind(&data));
+
+        assert_eq!(
+            wizard.render().into_string(),
+            concat!(
+                r#"<form action="/inscription" method="post">"#,
+                r#"<ol class="nav nav-pills mb-3">"#,
+                r#"<li class="nav-link">1. Identité</li>"#,
+                r#"<li class="nav-link active" aria-current="step">2. Contact</li>"#,
+                r#"<li class="nav-link disabled">3. Fin</li>"#,
+                r#"</ol>"#,
+                r#"<input type="hidden" name="_wizard_step" value="1">"#,
+                r#"<input type="hidden" name="nom" value="Dupont">"#,
+                r#"<div class="form-floating">"#,
+                r#"<input type="email" class="form-control" name="email" id="email" value="a@b.fr">"#,
+                r#"<label for="email">Email</label>"#,
+                r#"</div>"#,
+                r#"<div class="btn-toolbar" role="toolbar">"#,
+                r#"<button type="submit" class="btn btn-secondary" name="_wizard_nav" value="prev" formnovalidate>Précédent</button>"#,
+                r#"<button type="submit" class="btn btn-primary" name="_wizard_nav" value="next">Suivant</button>"#,
+                r#"</div>"#,
+                r#"</form>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn labels() {
+        let html = wizard()
+            .current(2)
+            .prev_label("Retour")
+            .next_label("Continuer")
+            .finish_label("Valider")
+            .render()
+            .into_string();
+
+        assert!(html.contains(
+            r#"<button type="submit" class="btn btn-secondary" name="_wizard_nav" value="prev" formnovalidate>Retour</button>"#
+        ));
+        assert!(html.contains(
+            r#"<button type="submit" class="btn btn-primary" name="_wizard_nav" value="finish">Valider</button>"#
+        ));
+        assert!(wizard()
+            .next_label("Continuer")
+            .render()
+            .into_string()
+            .contains(r#"value="next">Continuer</button>"#));
+    }
+
+    #[test]
+    fn navigation() {
+        let wizard = wizard();
+        let submission =
+            WizardSubmission::from_urlencoded("_wizard_step=1&_wizard_nav=prev&nom=Dupont")
+                .unwrap();
+
+        assert_eq!(submission.nav, WizardNav::Prev);
+        assert_eq!(wizard.target(&submission), 0);
+
+        let submission = WizardSubmission::from_urlencoded("_wizard_step=1&email=x").unwrap();
+        assert_eq!(wizard.target(&submission), 2);
+        assert!(wizard.is_last(2));
+        assert!(matches!(
+            Wizard::parse_step::<Contact>("nom=Dupont&email=x"),
+            Err(SchemaError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn signed() {
+        let key = CsrfKey::new(b"secret");
+        let data = FormData::from_pairs(&[("nom", "Dupont"), ("tags", "a"), ("tags", "b")]);
+        let html = wizard()
+            .signed(&key)
+            .current(2)
+            .data(&data)
+            .render()
+            .into_string();
+
+        //les champs cachés sont re-soumis avec l'étape 2
+        let signature = html
+            .split(r#"name="_wizard_signature" value=""#)
+            .nth(1)
+            .and_then(|x| x.split('"').next())
+            .unwrap();
+        let body = |nom: &str| {
+            format!(
+                "_wizard_step=2&nom={nom}&tags%5B0%5D=a&tags%5B1%5D=b&_wizard_signature={signature}&_wizard_nav=finish"
+            )
+        };
+
+        assert!(html.contains(r#"<input type="hidden" name="tags[1]" value="b">"#));
+        let submission = WizardSubmission::from_urlencoded(&body("Dupont")).unwrap();
+        assert_eq!(wizard().signed(&key).verify(&submission), Ok(()));
+
+        let tampered = WizardSubmission::from_urlencoded(&body("Durand")).unwrap();
+        assert_eq!(
+            wizard().signed(&key).verify(&tampered),
+            Err(WizardError::BadSignature)
+        );
+
+        let unsigned = WizardSubmission::from_urlencoded("_wizard_step=2&nom=Dupont").unwrap();
+        assert_eq!(
+            wizard().signed(&key).verify(&unsigned),
+            Err(WizardError::MissingSignature)
+        );
+    }
+
+    #[test]
+    fn finish_with_vec() {
+        #[derive(Debug, Deserialize, Validate)]
+        struct Inscription {
+            #[validate(length(min = 2))]
+            nom: String,
+            email: String,
+            tags: Vec<String>,
+        }
+
+        let tags = [KeyValue::new("a", "A"), KeyValue::new("b", "B")];
+        let schema = FormSchema::new()
+            .field(FieldSchema::text("nom", "Nom"))
+            .field(FieldSchema::email("email", "Email"))
+            .field(FieldSchema::multi_select("tags", "Tags", &tags));
+        let key = CsrfKey::new(b"secret");
+
+        //étape 2 soumise : nom repris, email et tags saisis
+        let data = FormData::from_urlencoded("nom=Dupont&email=a%40b.fr&tags=a&tags=b").unwrap();
+        let html = wizard()
+            .signed(&key)
+            .current(2)
+            .data(&data)
+            .render()
+            .into_string();
+
+        //corps envoyé par le navigateur : les champs cachés et le bouton Terminer
+        let mut pairs = html
+            .split(r#"<input type="hidden" name=""#)
+            .skip(1)
+            .filter_map(|x| {
+                let (name, rest) = x.split_once(r#"" value=""#)?;
+                Some((name.to_owned(), rest.split('"').next()?.to_owned()))
+            })
+            .collect::<Vec<_>>();
+        pairs.push((WIZARD_NAV.to_owned(), "finish".to_owned()));
+        let body = serde_urlencoded::to_string(&pairs).unwrap();
+
+        let submission = WizardSubmission::from_urlencoded(&body).unwrap();
+        assert_eq!(wizard().signed(&key).verify(&submission), Ok(()));
+        assert!(serde_urlencoded::from_str::<Inscription>(&body).is_err());
+
+        let inscription = submission.parse::<Inscription>(&schema).unwrap();
+        assert_eq!(inscription.nom, "Dupont");
+        assert_eq!(inscription.email, "a@b.fr");
+        assert_eq!(inscription.tags, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn errors_of_step() {
+        #[derive(Validate)]
+        struct Inscription {
+            #[validate(length(min = 2))]
+            nom: String,
+            #[validate(email)]
+            email: String,
+        }
+
+        let errors = Inscription {
+            nom: "D".to_owned(),
+            email: "x".to_owned(),
+        }
+        .validate()
+        .unwrap_err();
+        let errors = wizard().step_errors(1, &errors);
+
+        assert!(errors.field_errors().contains_key("email"));
+        assert!(!errors.field_errors().contains_key("nom"));
+    }
+}